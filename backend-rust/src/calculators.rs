@@ -1,286 +1,702 @@
 use crate::models::*;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::HashMap;
+
+/// A supported currency, see `CURRENCIES`.
+#[derive(Debug, PartialEq)]
+pub struct Currency {
+    pub code: &'static str,
+    pub symbol: &'static str,
+    /// Hryvnia and zloty are written "1 234,56 ₴", the others "$1,234.56".
+    pub symbol_after: bool,
+    /// Decimal places when the request doesn't set `precision`.
+    pub precision: u32,
+}
+
+/// Every supported currency; the first one is the fallback for unknown codes.
+pub const CURRENCIES: &[Currency] = &[
+    Currency { code: "EUR", symbol: "€", symbol_after: false, precision: 2 },
+    Currency { code: "USD", symbol: "$", symbol_after: false, precision: 2 },
+    Currency { code: "UAH", symbol: "₴", symbol_after: true, precision: 2 },
+    Currency { code: "GBP", symbol: "£", symbol_after: false, precision: 2 },
+    Currency { code: "PLN", symbol: "zł", symbol_after: true, precision: 2 },
+    // Yen have no minor unit in everyday use
+    Currency { code: "JPY", symbol: "¥", symbol_after: false, precision: 0 },
+    // Satoshis
+    Currency { code: "BTC", symbol: "₿", symbol_after: false, precision: 8 },
+];
+
+/// Strict currency lookup, case-insensitive, rejecting unknown codes.
+pub fn parse_currency(code: &str) -> Result<&'static Currency, String> {
+    CURRENCIES
+        .iter()
+        .find(|c| c.code.eq_ignore_ascii_case(code))
+        .ok_or_else(|| format!("Unknown currency: {}", code))
+}
+
+/// Lenient lookup, falls back to euro for unknown codes.
+fn currency_or_default(code: &str) -> &'static Currency {
+    parse_currency(code).unwrap_or(&CURRENCIES[0])
+}
+
+/// Converts between currencies using `rates`, given as units of each currency
+/// per one unit of a common base (the base itself should be listed as 1.0).
+pub fn convert(amount: f64, from: &str, to: &str, rates: &HashMap<String, f64>) -> Result<f64, String> {
+    let rate = |code: &str| {
+        rates
+            .get(&code.to_ascii_uppercase())
+            .copied()
+            .filter(|r| *r > 0.0)
+            .ok_or_else(|| format!("Missing exchange rate for {}", code))
+    };
+    Ok(amount / rate(from)? * rate(to)?)
+}
 
 pub fn get_currency_symbol(currency: &str) -> String {
-    match currency {
-        "EUR" => "€".to_string(),
-        "USD" => "$".to_string(),
-        "UAH" => "₴".to_string(),
-        "BTC" => "₿".to_string(),
-        _ => "€".to_string(),
+    currency_or_default(currency).symbol.to_string()
+}
+
+/// Formats an amount with grouped thousands and the currency symbol where the
+/// currency's convention puts it, e.g. "$1,234.56" or "1 234,56 ₴".
+pub fn format_money(amount: f64, currency: &str) -> String {
+    let precision = money_precision(None, currency) as usize;
+    let currency = currency_or_default(currency);
+    let (group, decimal) = if currency.symbol_after { (' ', ',') } else { (',', '.') };
+
+    let digits = format!("{:.*}", precision, sanitize(amount).abs());
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((&digits, ""));
+    let mut number = String::new();
+    for (i, c) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            number.push(group);
+        }
+        number.push(c);
+    }
+    if !frac_part.is_empty() {
+        number.push(decimal);
+        number.push_str(frac_part);
+    }
+
+    // No "-0.00" for amounts that round to zero
+    let sign = if amount < 0.0 && digits.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+    if currency.symbol_after {
+        format!("{}{} {}", sign, number, currency.symbol)
+    } else {
+        format!("{}{}{}", sign, currency.symbol, number)
     }
 }
 
-fn create_bar_chart(title: &str, labels: Vec<&str>, values: Vec<f64>, colors: Vec<&str>) -> String {
-    let width = 400;
-    let height = 300;
-    let padding = 40;
-    let chart_width = width - padding * 2;
-    let chart_height = height - padding * 2;
-    
-    let max_val = values.iter().cloned().fold(0.0, f64::max);
-    let scale = if max_val > 0.0 { chart_height as f64 / max_val } else { 1.0 };
-    
-    let bar_width = chart_width / labels.len() as i32 - 10;
-    
-    let mut svg = format!(
-        r#"<svg width="{}" height="{}" viewBox="0 0 {} {}" xmlns="http://www.w3.org/2000/svg">"#,
-        width, height, width, height
-    );
-    
-    // Background
-    svg.push_str(r#"<rect width="100%" height="100%" fill="white" />"#);
-    
-    // Title
-    svg.push_str(&format!(
-        r#"<text x="{}" y="25" font-family="sans-serif" font-size="16" font-weight="bold" text-anchor="middle">{}</text>"#,
-        width / 2, title
-    ));
-    
-    for (i, (&label, &value)) in labels.iter().zip(values.iter()).enumerate() {
-        let x = padding + i as i32 * (bar_width + 10) + 5;
-        let h = (value * scale) as i32;
-        let y = height - padding - h;
-        let color = colors.get(i).unwrap_or(&"#3498db");
-        
-        svg.push_str(&format!(
-            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="4" />"#,
-            x, y, bar_width, h, color
-        ));
-        
-        svg.push_str(&format!(
-            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="middle">{}</text>"#,
-            x + bar_width / 2, height - padding + 15, label
-        ));
-        
-        svg.push_str(&format!(
-            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" font-weight="bold" text-anchor="middle">{}</text>"#,
-            x + bar_width / 2, y - 5, value.round()
-        ));
+/// Rounds `value` to `precision` decimal places.
+pub fn round_to(value: f64, precision: u32) -> f64 {
+    let factor = 10f64.powi(precision as i32);
+    (value * factor).round() / factor
+}
+
+/// Decimal places for money amounts: the requested precision, otherwise the
+/// currency's own, e.g. satoshis for BTC and cents for everything else.
+pub fn money_precision(precision: Option<u8>, currency: &str) -> u32 {
+    match precision {
+        Some(p) => p.min(12) as u32,
+        None => currency_or_default(currency).precision,
     }
-    
-    svg.push_str("</svg>");
-    svg
 }
 
-pub fn calculate_hourly_income(req: HourlyIncomeRequest) -> HourlyIncomeResponse {
-    let net_monthly = req.monthly_income * (1.0 - req.taxes / 100.0) - req.work_expenses;
-    let total_hours = req.work_hours + req.commute_time;
-    let real_hourly = net_monthly / total_hours;
-    let nom_hourly = req.monthly_income / req.work_hours;
-    let efficiency = (real_hourly / nom_hourly) * 100.0;
+/// Maps NaN and infinite results to 0.0 so they never serialize as `null`.
+pub fn sanitize(value: f64) -> f64 {
+    if value.is_finite() { value } else { 0.0 }
+}
+
+/// Income left after taxes (in percent) and work expenses.
+fn net_income(gross: f64, tax_rate: f64, expenses: f64) -> f64 {
+    gross * (1.0 - tax_rate / 100.0) - expenses
+}
+
+/// Inverse of `net_income`: the gross income needed to keep `net`.
+fn gross_income(net: f64, tax_rate: f64, expenses: f64) -> f64 {
+    (net + expenses) / (1.0 - tax_rate / 100.0)
+}
 
-    let chart = create_bar_chart(
-        "Порівняння ставок",
-        vec!["Номінальна", "Реальна"],
-        vec![nom_hourly, real_hourly],
-        vec!["#95a5a6", "#2ecc71"]
-    );
+pub fn calculate_hourly_income(req: &HourlyIncomeRequest) -> Result<HourlyIncomeResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
 
-    HourlyIncomeResponse {
-        real_hourly_income: (real_hourly * 100.0).round() / 100.0,
-        nominal_hourly_income: (nom_hourly * 100.0).round() / 100.0,
-        net_income: (net_monthly * 100.0).round() / 100.0,
-        efficiency: (efficiency * 10.0).round() / 10.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+    if req.work_hours <= 0.0 {
+        return Err("work_hours must be greater than zero".into());
     }
+
+    let net_monthly = net_income(req.monthly_income, req.taxes, req.work_expenses);
+    let total_hours = req.work_hours + req.commute_time;
+    let real_hourly = sanitize(net_monthly / total_hours);
+    let nom_hourly = sanitize(req.monthly_income / req.work_hours);
+    let efficiency = sanitize((real_hourly / nom_hourly) * 100.0);
+
+    Ok(HourlyIncomeResponse {
+        real_hourly_income: round_to(real_hourly, precision),
+        nominal_hourly_income: round_to(nom_hourly, precision),
+        net_income: round_to(net_monthly, precision),
+        efficiency: round_to(efficiency, 1),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
 }
 
-pub fn calculate_time_value(req: TimeValueRequest) -> TimeValueResponse {
-    let hourly = req.annual_income / req.annual_hours;
-    
-    let chart = create_bar_chart(
-        "Вартість часу",
-        vec!["Година", "День", "Тиждень", "Місяць"],
-        vec![hourly, hourly * 8.0, hourly * 40.0, hourly * 160.0],
-        vec!["#3498db", "#3498db", "#3498db", "#3498db"]
-    );
+pub fn calculate_time_value(req: &TimeValueRequest) -> Result<TimeValueResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
 
-    TimeValueResponse {
-        time_value: (hourly * 100.0).round() / 100.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+    if req.annual_hours <= 0.0 {
+        return Err("annual_hours must be greater than zero".into());
     }
+
+    let hourly = sanitize(req.annual_income / req.annual_hours);
+    // Purchasing power parity: scale into the reference country's prices
+    let adjusted = hourly * req.ppp_factor.unwrap_or(1.0);
+
+    Ok(TimeValueResponse {
+        time_value: round_to(hourly, precision),
+        ppp_adjusted_value: round_to(adjusted, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
 }
 
-pub fn calculate_investment(req: InvestmentRequest) -> InvestmentResponse {
-    let r = req.annual_return / 100.0 / 12.0;
-    let n = (req.period * 12.0) as i32;
-    
-    let fv = if r > 0.0 {
-        req.initial_amount * (1.0 + r).powi(n) + req.monthly_contribution * (((1.0 + r).powi(n) - 1.0) / r)
+fn compounding_periods(compounding: Option<&str>) -> Result<f64, String> {
+    match compounding.unwrap_or("monthly") {
+        "monthly" => Ok(12.0),
+        "quarterly" => Ok(4.0),
+        "annual" => Ok(1.0),
+        other => Err(format!("Unknown compounding: {} (expected monthly, quarterly or annual)", other)),
+    }
+}
+
+/// Future value of a lump sum plus a fixed contribution at the end of each period.
+/// Negative rates compound too; only a rate of exactly zero is a plain sum.
+fn future_value(initial: f64, contribution: f64, r: f64, n: i32) -> f64 {
+    if r != 0.0 {
+        initial * (1.0 + r).powi(n) + contribution * (((1.0 + r).powi(n) - 1.0) / r)
     } else {
-        req.initial_amount + req.monthly_contribution * n as f64
-    };
+        initial + contribution * n as f64
+    }
+}
+
+/// Contribution per period as an end-of-period equivalent: paid at the start
+/// instead (annuity due), it earns one more period of growth.
+fn contribution_timing(contribution: f64, r: f64, annuity_due: Option<bool>) -> f64 {
+    if annuity_due.unwrap_or(false) { contribution * (1.0 + r) } else { contribution }
+}
+
+/// Portfolio value after each compounding period, in years.
+pub fn investment_growth(req: &InvestmentRequest) -> Result<Vec<(f64, f64)>, String> {
+    let periods_per_year = compounding_periods(req.compounding.as_deref())?;
+    let r = req.annual_return / 100.0 / periods_per_year;
+    let n = (req.period * periods_per_year) as i32;
+    let contribution = contribution_timing(req.monthly_contribution * 12.0 / periods_per_year, r, req.annuity_due);
+
+    Ok((0..=n.max(0))
+        .map(|k| (k as f64 / periods_per_year, future_value(req.initial_amount, contribution, r, k)))
+        .collect())
+}
+
+/// A period of 0 leaves the initial amount as it is: no compounding steps, so
+/// the future value equals it, the gain is 0 and so is the ROI.
+pub fn calculate_investment(req: &InvestmentRequest) -> Result<InvestmentResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let periods_per_year = compounding_periods(req.compounding.as_deref())?;
+    let r = req.annual_return / 100.0 / periods_per_year;
+    let n = (req.period * periods_per_year) as i32;
+    let contribution = req.monthly_contribution * 12.0 / periods_per_year;
+
+    let fv = future_value(req.initial_amount, contribution_timing(contribution, r, req.annuity_due), r, n);
     
-    let total_inv = req.initial_amount + req.monthly_contribution * n as f64;
+    let total_inv = req.initial_amount + contribution * n as f64;
     let gain = fv - total_inv;
     let roi = if total_inv > 0.0 { (gain / total_inv) * 100.0 } else { 0.0 };
 
-    // simplified "chart" for investment (just end state comparison)
-    let chart = create_bar_chart(
-        "Структура капіталу",
-        vec!["Внески", "Прибуток"],
-        vec![total_inv, gain],
-        vec!["#3498db", "#2ecc71"]
-    );
+    // Future value in today's money, discounted by inflation over the period
+    let inflation_factor = (1.0 + req.inflation_rate.unwrap_or(0.0) / 100.0).powf(req.period.max(0.0));
+    let real_fv = fv / inflation_factor;
+    let real_roi = if total_inv > 0.0 { ((real_fv - total_inv) / total_inv) * 100.0 } else { 0.0 };
+
+    // Sensitivity view: the same plan at a pessimistic and an optimistic return
+    let scenarios = if req.return_low.is_some() || req.return_high.is_some() {
+        let fv_at = |annual_return: f64| {
+            let r = annual_return / 100.0 / periods_per_year;
+            round_to(future_value(req.initial_amount, contribution_timing(contribution, r, req.annuity_due), r, n), precision)
+        };
+        Some(InvestmentScenarios {
+            low: fv_at(req.return_low.unwrap_or(req.annual_return)),
+            expected: round_to(fv, precision),
+            high: fv_at(req.return_high.unwrap_or(req.annual_return)),
+        })
+    } else {
+        None
+    };
+
+    Ok(InvestmentResponse {
+        future_value: round_to(fv, precision),
+        total_contributions: round_to(total_inv, precision),
+        total_gain: round_to(gain, precision),
+        roi: round_to(roi, 1),
+        real_future_value: round_to(real_fv, precision),
+        real_roi: round_to(real_roi, 1),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+        growth_chart: None,
+        scenarios,
+        scenario_chart: None,
+    })
+}
+
+/// Rounds a decimal amount to the smallest currency unit (cents by default).
+fn cents(value: Decimal, precision: u32) -> Decimal {
+    value.round_dp_with_strategy(precision, RoundingStrategy::MidpointAwayFromZero)
+}
+
+fn to_money(value: f64, precision: u32) -> Decimal {
+    cents(Decimal::from_f64(value).unwrap_or_default(), precision)
+}
+
+/// Builds a cent-exact amortization schedule using decimal arithmetic,
+/// returning the rows and the total amount paid.
+fn amortization_schedule(amount: f64, r: f64, months: u32, pmt: f64, precision: u32) -> (Vec<AmortizationRow>, Decimal) {
+    let rate = Decimal::from_f64(r).unwrap_or_default();
+    let payment = to_money(pmt, precision);
+    let mut balance = to_money(amount, precision);
+    let mut total_paid = Decimal::ZERO;
+    let mut rows = Vec::with_capacity(months as usize);
 
-    InvestmentResponse {
-        future_value: (fv * 100.0).round() / 100.0,
-        total_contributions: (total_inv * 100.0).round() / 100.0,
-        total_gain: (gain * 100.0).round() / 100.0,
-        roi: (roi * 10.0).round() / 10.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+    for month in 1..=months {
+        let interest = cents(balance * rate, precision);
+        // The last row pays off whatever is left so the loan closes at exactly zero
+        let principal = if month == months { balance } else { (payment - interest).min(balance) };
+        balance -= principal;
+        total_paid += principal + interest;
+
+        rows.push(AmortizationRow {
+            month,
+            payment: (principal + interest).to_f64().unwrap_or(0.0),
+            principal: principal.to_f64().unwrap_or(0.0),
+            interest: interest.to_f64().unwrap_or(0.0),
+            remaining_balance: balance.to_f64().unwrap_or(0.0),
+        });
     }
+
+    (rows, total_paid)
 }
 
-pub fn calculate_credit(req: CreditRequest) -> CreditResponse {
-    let r = req.rate / 100.0 / 12.0;
-    let n = req.term * 12.0;
-    
-    let pmt = if r > 0.0 && n > 0.0 {
-        req.amount * (r * (1.0 + r).powf(n)) / ((1.0 + r).powf(n) - 1.0)
+/// Annuity payment repaying `amount` over `n` periods at per-period rate `r`.
+fn annuity_payment(amount: f64, r: f64, n: f64) -> f64 {
+    if r > 0.0 && n > 0.0 {
+        amount * (r * (1.0 + r).powf(n)) / ((1.0 + r).powf(n) - 1.0)
     } else if n > 0.0 {
-        req.amount / n
+        amount / n
     } else {
         0.0
-    };
-    
-    let total = pmt * n;
-    let overpayment = total - req.amount;
+    }
+}
 
-    let chart = create_bar_chart(
-        "Структура виплат",
-        vec!["Тіло", "Переплата"],
-        vec![req.amount, overpayment],
-        vec!["#3498db", "#e74c3c"]
-    );
+/// Loan a fixed payment pays off over `n` periods at rate `r`, the inverse of `annuity_payment`.
+fn annuity_principal(payment: f64, r: f64, n: f64) -> f64 {
+    if r > 0.0 && n > 0.0 {
+        payment * ((1.0 + r).powf(n) - 1.0) / (r * (1.0 + r).powf(n))
+    } else if n > 0.0 {
+        payment * n
+    } else {
+        0.0
+    }
+}
 
-    CreditResponse {
-        monthly_payment: (pmt * 100.0).round() / 100.0,
-        total_payment: (total * 100.0).round() / 100.0,
-        overpayment: (overpayment * 100.0).round() / 100.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+/// Payments per year for a credit's `payment_frequency`, monthly by default.
+fn payments_per_year(frequency: Option<&str>) -> Result<f64, String> {
+    match frequency.unwrap_or("monthly") {
+        "monthly" => Ok(12.0),
+        "biweekly" => Ok(26.0),
+        other => Err(format!("Unknown payment_frequency: {} (expected monthly or biweekly)", other)),
     }
 }
 
-pub fn calculate_retirement(req: RetirementRequest) -> RetirementResponse {
+pub fn calculate_credit(req: &CreditRequest) -> Result<CreditResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let periods_per_year = payments_per_year(req.payment_frequency.as_deref())?;
+    let r = req.rate / 100.0 / periods_per_year;
+    let n = req.term * periods_per_year;
+    let pmt = annuity_payment(req.amount, r, n);
+    
+    // Totals come from the cent-exact schedule rather than `pmt * n`
+    let (rows, total_paid) = amortization_schedule(req.amount, r, n.round().max(0.0) as u32, pmt, precision);
+    let total = total_paid.to_f64().unwrap_or(0.0);
+    let overpayment = (total_paid - to_money(req.amount, precision)).to_f64().unwrap_or(0.0);
+
+    let (first_month_interest, first_month_principal) = rows.first().map_or((0.0, 0.0), |row| (row.interest, row.principal));
+    let schedule = if req.include_schedule.unwrap_or(false) { Some(rows) } else { None };
+
+    // Nominal rate compounded once per payment, expressed as a yearly percentage (APY)
+    let effective_annual_rate = ((1.0 + r).powf(periods_per_year) - 1.0) * 100.0;
+
+    // Biweekly loans also report what their payments add up to per month
+    let biweekly_payment = (periods_per_year == 26.0).then(|| round_to(pmt, precision));
+
+    Ok(CreditResponse {
+        monthly_payment: round_to(pmt * periods_per_year / 12.0, precision),
+        biweekly_payment,
+        total_payment: round_to(total, precision),
+        overpayment: round_to(overpayment, precision),
+        effective_annual_rate: round_to(effective_annual_rate, 2),
+        first_month_interest,
+        first_month_principal,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+        schedule,
+    })
+}
+
+/// Safe withdrawal rate, in percent, when the request doesn't set one.
+const DEFAULT_WITHDRAWAL_RATE: f64 = 4.0;
+
+/// Capital that pays `monthly_income` forever when `withdrawal_rate` percent
+/// of it is drawn down each year.
+fn retirement_capital(monthly_income: f64, withdrawal_rate: f64) -> f64 {
+    (monthly_income * 12.0) / (withdrawal_rate / 100.0)
+}
+
+pub fn calculate_retirement(req: &RetirementRequest) -> RetirementResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
     let years_to_save = req.retirement_age - req.current_age;
     let r = req.expected_return / 100.0 / 12.0;
     let n = (years_to_save * 12.0) as i32;
     
     let fv_existing = req.current_savings * (1.0 + r).powi(n);
+    let monthly_savings = contribution_timing(req.monthly_savings, r, req.annuity_due);
     let fv_monthly = if r > 0.0 {
-        req.monthly_savings * (((1.0 + r).powi(n) - 1.0) / r)
+        monthly_savings * (((1.0 + r).powi(n) - 1.0) / r)
     } else {
-        req.monthly_savings * n as f64
+        monthly_savings * n as f64
     };
     
     let total_fv = fv_existing + fv_monthly;
-    let required_capital = (req.desired_income * 12.0) / 0.04;
+    let withdrawal_rate = req.withdrawal_rate.unwrap_or(DEFAULT_WITHDRAWAL_RATE);
+    let required_capital = retirement_capital(req.desired_income, withdrawal_rate);
     let gap = (required_capital - total_fv).max(0.0);
 
-    let chart = create_bar_chart(
-        "Пенсійне забезпечення",
-        vec!["Матимете", "Необхідно"],
-        vec![total_fv, required_capital],
-        vec!["#2ecc71", "#e67e22"]
-    );
+    // Inflation over the saving years: the projection is deflated to today's money,
+    // while the desired income (given in today's money) is inflated to retirement.
+    let inflation_factor = (1.0 + req.inflation_rate.unwrap_or(0.0) / 100.0).powf(years_to_save.max(0.0));
+    let future_value_real = total_fv / inflation_factor;
+    let required_capital_real = required_capital * inflation_factor;
 
     RetirementResponse {
-        future_value: (total_fv * 100.0).round() / 100.0,
-        required_capital: (required_capital * 100.0).round() / 100.0,
-        gap: (gap * 100.0).round() / 100.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+        future_value: round_to(total_fv, precision),
+        future_value_savings: round_to(fv_existing, precision),
+        future_value_contributions: round_to(fv_monthly, precision),
+        required_capital: round_to(required_capital, precision),
+        gap: round_to(gap, precision),
+        future_value_real: round_to(future_value_real, precision),
+        required_capital_real: round_to(required_capital_real, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
     }
 }
 
-pub fn calculate_debt_payoff(req: DebtPayoffRequest) -> DebtPayoffResponse {
+/// Smallest monthly payment that keeps a loan from growing: the month's
+/// interest on `balance`. Exactly this only services the interest, so the
+/// balance never shrinks either.
+fn min_viable_payment(balance: f64, monthly_rate: f64) -> f64 {
+    balance * monthly_rate
+}
+
+/// Refuses a monthly `payment` below the month's `interest`, the error naming
+/// the payment as `what`. Exactly the interest passes; it services the debt
+/// without shrinking it. A NaN payment never passes.
+fn check_covers_interest(what: &str, payment: f64, interest: f64, precision: u32) -> Result<(), String> {
+    if payment >= interest {
+        Ok(())
+    } else {
+        Err(format!("{} must be at least the monthly interest of {}", what, round_to(interest, precision)))
+    }
+}
+
+/// Safety cap for the month-by-month debt simulations (100 years).
+const MAX_PAYOFF_MONTHS: u32 = 1200;
+
+/// Balance left and interest paid so far after each month of paying the debt
+/// down, in whole currency units like `amortization_schedule`; the last payment
+/// only settles what is left. `None` when it takes over `MAX_PAYOFF_MONTHS`.
+fn payoff_schedule(req: &DebtPayoffRequest, precision: u32) -> Option<Vec<(Decimal, Decimal)>> {
+    let rate = Decimal::from_f64(req.interest_rate / 100.0 / 12.0).unwrap_or_default();
+    let payment = to_money(req.monthly_payment + req.extra_payment, precision);
+    let mut balance = to_money(req.balance, precision);
+    let mut interest_paid = Decimal::ZERO;
+    let mut schedule = Vec::new();
+
+    while balance > Decimal::ZERO {
+        if schedule.len() == MAX_PAYOFF_MONTHS as usize {
+            return None;
+        }
+        let interest = cents(balance * rate, precision);
+        balance += interest;
+        interest_paid += interest;
+        balance -= payment.min(balance);
+        schedule.push((balance, interest_paid));
+    }
+
+    Some(schedule)
+}
+
+pub fn calculate_debt_payoff(req: &DebtPayoffRequest) -> Result<DebtPayoffResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
     let r = req.interest_rate / 100.0 / 12.0;
     let p = req.monthly_payment + req.extra_payment;
+    let monthly_interest = min_viable_payment(req.balance, r);
+    // extra_payment counts towards it
+    check_covers_interest("monthly_payment", p, monthly_interest, precision)?;
     
-    if p <= req.balance * r {
-        return DebtPayoffResponse {
-            months: 999,
+    if p <= monthly_interest {
+        // Smallest payment in the currency's smallest unit that reduces the balance
+        let unit = 10f64.powi(-(precision as i32));
+        let minimum_payment_required = (monthly_interest / unit).floor() * unit + unit;
+
+        return Ok(DebtPayoffResponse {
+            months: 0,
             total_paid: 0.0,
             total_interest: 0.0,
-            currency_symbol: get_currency_symbol(&req.currency),
-            chart: "<svg></svg>".into(),
-        };
+            payable: false,
+            minimum_payment_required: Some(round_to(minimum_payment_required, precision)),
+            currency_symbol: get_currency_symbol(&req.options.currency),
+            chart: String::new(),
+            timeline_chart: None,
+        });
     }
     
-    let months = (p / (p - req.balance * r)).ln() / (1.0 + r).ln();
-    let total_paid = p * months;
-    let total_interest = total_paid - req.balance;
+    let Some(schedule) = payoff_schedule(req, precision) else {
+        return Err(format!("monthly_payment must pay off the balance within {} months", MAX_PAYOFF_MONTHS));
+    };
+    let total_interest = schedule.last().map_or(Decimal::ZERO, |&(_, interest)| interest);
+    let total_paid = to_money(req.balance, precision) + total_interest;
+
+    Ok(DebtPayoffResponse {
+        months: schedule.len() as u32,
+        total_paid: total_paid.to_f64().unwrap_or(0.0),
+        total_interest: total_interest.to_f64().unwrap_or(0.0),
+        payable: true,
+        minimum_payment_required: None,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+        timeline_chart: None,
+    })
+}
 
-    let chart = create_bar_chart(
-        "Структура боргу",
-        vec!["Борг", "Відсотки"],
-        vec![req.balance, total_interest],
-        vec!["#3498db", "#e74c3c"]
-    );
+/// Points in a debt payoff timeline, however long the payoff takes.
+const TIMELINE_POINTS: usize = 120;
 
-    DebtPayoffResponse {
-        months: months.ceil() as u32,
-        total_paid: (total_paid * 100.0).round() / 100.0,
-        total_interest: (total_interest * 100.0).round() / 100.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+/// Balance left and interest paid so far at evenly spaced months until the debt
+/// is paid off, the last point at payoff itself. Empty when it never is.
+pub fn debt_payoff_timeline(req: &DebtPayoffRequest) -> Vec<(f64, f64, f64)> {
+    let r = req.interest_rate / 100.0 / 12.0;
+    let p = req.monthly_payment + req.extra_payment;
+    if p <= min_viable_payment(req.balance, r) {
+        return Vec::new();
     }
+
+    let precision = money_precision(req.options.precision, &req.options.currency);
+    let Some(schedule) = payoff_schedule(req, precision) else {
+        return Vec::new();
+    };
+
+    let point = |month: usize| match month {
+        0 => (0.0, req.balance, 0.0),
+        _ => {
+            let (balance, interest) = schedule[month - 1];
+            (month as f64, balance.to_f64().unwrap_or(0.0), interest.to_f64().unwrap_or(0.0))
+        }
+    };
+
+    let months = schedule.len();
+    let step = months.div_ceil(TIMELINE_POINTS).max(1);
+    let mut points: Vec<(f64, f64, f64)> = (0..months).step_by(step).map(point).collect();
+    points.push(point(months));
+    points
 }
 
-pub fn calculate_emergency_fund(req: EmergencyFundRequest) -> EmergencyFundResponse {
-    let target = req.monthly_expenses * req.months_coverage;
-    let remaining = (target - req.current_savings).max(0.0);
-    
-    let months_to_target = if req.monthly_contribution > 0.0 {
-        remaining / req.monthly_contribution
+/// Pays all debts down month by month with a fixed total budget: minimums first,
+/// then everything left over goes to the debts in `order`. Returns months and interest paid.
+fn simulate_payoff(debts: &[Debt], extra_payment: f64, order: &[usize], precision: u32) -> StrategyResult {
+    let mut balances: Vec<f64> = debts.iter().map(|d| d.balance).collect();
+    let budget: f64 = debts.iter().map(|d| d.min_payment).sum::<f64>() + extra_payment;
+    let mut total_interest = 0.0;
+    let mut months = 0;
+
+    while balances.iter().any(|&b| b > 0.005) && months < MAX_PAYOFF_MONTHS {
+        months += 1;
+        let mut available = budget;
+
+        for (balance, debt) in balances.iter_mut().zip(debts) {
+            let interest = *balance * debt.rate / 100.0 / 12.0;
+            *balance += interest;
+            total_interest += interest;
+        }
+
+        for (balance, debt) in balances.iter_mut().zip(debts) {
+            let payment = debt.min_payment.min(*balance).min(available);
+            *balance -= payment;
+            available -= payment;
+        }
+
+        for &i in order {
+            let payment = available.min(balances[i]);
+            balances[i] -= payment;
+            available -= payment;
+        }
+    }
+
+    StrategyResult {
+        months,
+        total_interest: round_to(total_interest, precision),
+    }
+}
+
+pub fn calculate_debt_strategy(req: &DebtStrategyRequest) -> Result<DebtStrategyResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    if req.debts.is_empty() {
+        return Err("debts must not be empty".into());
+    }
+
+    let budget: f64 = req.debts.iter().map(|d| d.min_payment).sum::<f64>() + req.extra_payment;
+    let first_interest: f64 = req.debts.iter().map(|d| min_viable_payment(d.balance, d.rate / 100.0 / 12.0)).sum();
+    // The minimums and the extra payment together, against the interest on all debts
+    check_covers_interest("min_payment", budget, first_interest, precision)?;
+
+    // Snowball: smallest balance first. Avalanche: highest rate first.
+    let mut snowball_order: Vec<usize> = (0..req.debts.len()).collect();
+    snowball_order.sort_by(|&a, &b| req.debts[a].balance.total_cmp(&req.debts[b].balance));
+    let mut avalanche_order: Vec<usize> = (0..req.debts.len()).collect();
+    avalanche_order.sort_by(|&a, &b| req.debts[b].rate.total_cmp(&req.debts[a].rate));
+
+    let snowball = simulate_payoff(&req.debts, req.extra_payment, &snowball_order, precision);
+    let avalanche = simulate_payoff(&req.debts, req.extra_payment, &avalanche_order, precision);
+
+    let recommendation = if avalanche.total_interest < snowball.total_interest {
+        "avalanche"
     } else {
-        -1.0
+        "snowball"
     };
+    let interest_saved = (snowball.total_interest - avalanche.total_interest).abs();
 
-    let chart = create_bar_chart(
-        "Статус подушки",
-        vec!["Наявне", "Ціль"],
-        vec![req.current_savings, target],
-        vec!["#3498db", "#f1c40f"]
-    );
+    Ok(DebtStrategyResponse {
+        snowball,
+        avalanche,
+        recommendation: recommendation.to_string(),
+        interest_saved: round_to(interest_saved, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
 
-    EmergencyFundResponse {
-        target_amount: (target * 100.0).round() / 100.0,
-        remaining_amount: (remaining * 100.0).round() / 100.0,
-        months_to_target: (months_to_target * 10.0).round() / 10.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+/// Months of fixed contributions until `saved` reaches `target`: 0 when it
+/// already has, -1.0 when nothing is being contributed.
+fn months_to_save(target: f64, saved: f64, contribution: f64) -> f64 {
+    let remaining = (target - saved).max(0.0);
+    if remaining == 0.0 {
+        0.0
+    } else if contribution > 0.0 {
+        sanitize(remaining / contribution)
+    } else {
+        -1.0
     }
 }
 
-pub fn calculate_tax(req: TaxRequest) -> TaxResponse {
-    let rate = req.tax_rate / 100.0;
-    
-    let tax_amount = req.income * rate;
-    let net_income = req.income - tax_amount;
+pub fn calculate_emergency_fund(req: &EmergencyFundRequest) -> Result<EmergencyFundResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    if req.months_coverage <= 0.0 {
+        return Err("months_coverage must be greater than zero".into());
+    }
+
+    let target = sanitize(req.monthly_expenses * req.months_coverage);
+    let remaining = (target - req.current_savings).max(0.0);
+    let months_to_target = months_to_save(target, req.current_savings, req.monthly_contribution);
+    let current_coverage_months =
+        (req.monthly_expenses > 0.0).then(|| round_to(req.current_savings / req.monthly_expenses, 1));
+
+    Ok(EmergencyFundResponse {
+        target_amount: round_to(target, precision),
+        remaining_amount: round_to(remaining, precision),
+        months_to_target: round_to(months_to_target, 1),
+        current_coverage_months,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
 
-    let chart = create_bar_chart(
-        "Структура доходу",
-        vec!["Чистий", "Податок"],
-        vec![net_income, tax_amount],
-        vec!["#2ecc71", "#e74c3c"]
-    );
+/// Upper end of band `i`; the last band is open-ended whatever its `up_to`,
+/// so income past it is still taxed at the last rate.
+fn band_upper(brackets: &[TaxBracket], i: usize) -> f64 {
+    match brackets[i].up_to {
+        Some(up_to) if i + 1 < brackets.len() => up_to,
+        _ => f64::INFINITY,
+    }
+}
+
+fn progressive_tax(income: f64, brackets: &[TaxBracket]) -> f64 {
+    let mut tax = 0.0;
+    let mut lower = 0.0;
+    for (i, bracket) in brackets.iter().enumerate() {
+        if income <= lower {
+            break;
+        }
+        let upper = band_upper(brackets, i);
+        tax += (income.min(upper) - lower) * bracket.rate / 100.0;
+        lower = upper;
+    }
+    tax
+}
+
+/// Rate of the band the last unit of `income` falls in; 0 when nothing is taxable.
+fn marginal_rate(income: f64, brackets: &[TaxBracket]) -> f64 {
+    let mut lower = 0.0;
+    for (i, bracket) in brackets.iter().enumerate() {
+        let upper = band_upper(brackets, i);
+        if income > lower && income <= upper {
+            return bracket.rate;
+        }
+        lower = upper;
+    }
+    0.0
+}
+
+pub fn calculate_tax(req: &TaxRequest) -> TaxResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    // Only income above the tax-free allowance is taxed
+    let taxable = (req.income - req.allowance.unwrap_or(0.0)).max(0.0);
+    let tax_amount = match &req.brackets {
+        Some(brackets) => progressive_tax(taxable, brackets),
+        None => taxable * req.tax_rate / 100.0,
+    };
+    let effective_rate = if req.income > 0.0 { tax_amount / req.income * 100.0 } else { 0.0 };
+    let marginal_rate = match &req.brackets {
+        Some(brackets) => marginal_rate(taxable, brackets),
+        None if taxable > 0.0 => req.tax_rate,
+        None => 0.0,
+    };
+    let net_income = req.income - tax_amount;
 
     TaxResponse {
-        tax_amount: (tax_amount * 100.0).round() / 100.0,
-        net_income: (net_income * 100.0).round() / 100.0,
-        effective_rate: (rate * 100.0 * 10.0).round() / 10.0,
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+        tax_amount: round_to(tax_amount, precision),
+        net_income: round_to(net_income, precision),
+        effective_rate: round_to(effective_rate, 1),
+        marginal_rate: round_to(marginal_rate, 2),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
     }
 }
 
-pub fn calculate_buy_rent(req: BuyRentRequest) -> BuyRentResponse {
+pub fn calculate_buy_rent(req: &BuyRentRequest) -> Result<BuyRentResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
     let loan = (req.property_price - req.down_payment).max(0.0);
     let r = req.mortgage_rate / 100.0 / 12.0;
     let n = (req.mortgage_term * 12.0) as i32;
@@ -292,37 +708,1322 @@ pub fn calculate_buy_rent(req: BuyRentRequest) -> BuyRentResponse {
     } else {
         0.0
     };
-    
-    let mut buy_costs_total = req.down_payment;
-    for _ in 1..=(req.horizon as i32 * 12) {
-        buy_costs_total += mp + (req.property_price * 0.01 / 12.0);
+
+    // An overflowing annuity factor (very long terms at high rates) leaves a
+    // payment that no longer covers the interest, so the loan would only grow
+    if loan > 0.0 {
+        check_covers_interest("mortgage payment", mp, min_viable_payment(loan, r), precision)?;
     }
     
+    // Net positions after `years`: whatever is still owed on the mortgage
+    // reduces the owner's equity, the renter invests the down payment at 7%.
+    let net_buy_at = |years: f64, balance: f64, buy_costs: f64| {
+        req.property_price * (1.0 + req.property_growth / 100.0).powf(years) - balance - buy_costs
+    };
+    let net_rent_at = |years: f64, rent_costs: f64| req.down_payment * (1.07_f64).powf(years) - rent_costs;
+
+    let mut buy_costs_total = req.down_payment;
+    let mut balance = loan;
+    let mut interest_paid = 0.0;
+    let overpayment = req.monthly_overpayment.unwrap_or(0.0).max(0.0);
     let mut rent_costs_total = 0.0;
     let mut curr_rent = req.monthly_rent;
+    let mut break_even_year = None;
     for m in 1..=(req.horizon as i32 * 12) {
+        // Mortgage payments stop once the loan is paid off
+        if balance > 0.0 {
+            let interest = balance * r;
+            let payment = (mp + overpayment).min(balance + interest);
+            balance -= payment - interest;
+            interest_paid += interest;
+            buy_costs_total += payment;
+        }
+        buy_costs_total += req.property_price * 0.01 / 12.0;
         rent_costs_total += curr_rent;
+
         if m % 12 == 0 {
             curr_rent *= 1.0 + req.rent_growth / 100.0;
+
+            let year = (m / 12) as f64;
+            if break_even_year.is_none()
+                && net_buy_at(year, balance, buy_costs_total) > net_rent_at(year, rent_costs_total)
+            {
+                break_even_year = Some(year);
+            }
         }
     }
     
-    let final_prop_val = req.property_price * (1.0 + req.property_growth / 100.0).powf(req.horizon);
-    let net_buy = final_prop_val - buy_costs_total;
-    let net_rent = req.down_payment * (1.07_f64).powf(req.horizon) - rent_costs_total;
-    
-    let chart = create_bar_chart(
-        "Капітал через горизонт",
-        vec!["Купівля", "Оренда"],
-        vec![net_buy, net_rent],
-        vec!["#2ecc71", "#3498db"]
-    );
-
-    BuyRentResponse {
-        net_buy_position: (net_buy * 100.0).round() / 100.0,
-        net_rent_position: (net_rent * 100.0).round() / 100.0,
+    let months_to_down_payment = if req.current_down_savings.is_some() || req.monthly_down_contribution.is_some() {
+        let saved = req.current_down_savings.unwrap_or(0.0);
+        let contribution = req.monthly_down_contribution.unwrap_or(0.0);
+        Some(round_to(months_to_save(req.down_payment, saved, contribution), 1))
+    } else {
+        None
+    };
+
+    let net_buy = net_buy_at(req.horizon, balance, buy_costs_total);
+    let net_rent = net_rent_at(req.horizon, rent_costs_total);
+
+    Ok(BuyRentResponse {
+        net_buy_position: round_to(net_buy, precision),
+        net_rent_position: round_to(net_rent, precision),
         recommendation: if net_buy > net_rent { "buy".to_string() } else { "rent".to_string() },
-        currency_symbol: get_currency_symbol(&req.currency),
-        chart,
+        total_interest_paid: round_to(interest_paid, precision),
+        break_even_year,
+        months_to_down_payment,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+pub fn calculate_savings_rate(req: &SavingsRateRequest) -> Result<SavingsRateResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    if req.monthly_income <= 0.0 {
+        return Err("monthly_income must be greater than zero".into());
+    }
+
+    let monthly_savings = req.monthly_income - req.monthly_expenses;
+    let savings_rate = monthly_savings / req.monthly_income * 100.0;
+    let annual_savings = monthly_savings * 12.0;
+    // Financial independence at 25x annual expenses (the 4% rule)
+    let fi_target = req.monthly_expenses * 12.0 * 25.0;
+
+    let r = req.expected_return.unwrap_or(5.0) / 100.0 / 12.0;
+    let months = if monthly_savings <= 0.0 {
+        -1.0
+    } else if r > 0.0 {
+        (fi_target * r / monthly_savings + 1.0).ln() / (1.0 + r).ln()
+    } else {
+        fi_target / monthly_savings
+    };
+    let years_to_independence = if months < 0.0 { -1.0 } else { sanitize(months / 12.0) };
+
+    Ok(SavingsRateResponse {
+        savings_rate: round_to(savings_rate, 1),
+        annual_savings: round_to(annual_savings, precision),
+        fi_target: round_to(fi_target, precision),
+        years_to_independence: round_to(years_to_independence, 1),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+/// Horizon of the FIRE projection; beyond it the target is reported as never reached.
+const MAX_FIRE_YEARS: u32 = 100;
+
+/// Financial independence at 25x annual expenses (the 4% rule).
+const FIRE_MULTIPLE: f64 = 25.0;
+
+/// Net worth at the end of each year until it reaches the FIRE target.
+/// Savings are added at the end of each year.
+pub fn fire_projection(req: &FireRequest) -> Vec<(f64, f64)> {
+    let fire_target = req.annual_expenses * FIRE_MULTIPLE;
+    let r = req.expected_return / 100.0;
+
+    let mut net_worth = req.current_net_worth;
+    let mut points = vec![(0.0, net_worth)];
+    let mut year = 0;
+    while net_worth < fire_target && year < MAX_FIRE_YEARS {
+        net_worth = net_worth * (1.0 + r) + req.annual_savings;
+        year += 1;
+        points.push((year as f64, net_worth));
+    }
+    points
+}
+
+pub fn calculate_fire(req: &FireRequest) -> FireResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let fire_target = req.annual_expenses * FIRE_MULTIPLE;
+    let years_to_fire = match fire_projection(req)[..] {
+        [(_, start)] if start >= fire_target => 0.0,
+        // Interpolate within the year the target is crossed
+        [.., (_, previous), (year, last)] if last >= fire_target => {
+            year - 1.0 + (fire_target - previous) / (last - previous)
+        }
+        _ => -1.0,
+    };
+
+    let fire_age = match req.current_age {
+        Some(age) if years_to_fire >= 0.0 => Some(round_to(age + years_to_fire, 1)),
+        _ => None,
+    };
+
+    FireResponse {
+        fire_target: round_to(fire_target, precision),
+        years_to_fire: round_to(sanitize(years_to_fire), 1),
+        fire_age,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Value of a lump sum at each whole year, plus the exact end of `years`.
+pub fn compound_growth(req: &CompoundRequest) -> Result<Vec<(f64, f64)>, String> {
+    let periods_per_year = compounding_periods(req.compounding.as_deref())?;
+    let r = req.annual_rate / 100.0 / periods_per_year;
+    let value_at = |years: f64| req.principal * (1.0 + r).powf(periods_per_year * years);
+
+    let mut points: Vec<(f64, f64)> = (0..=req.years.max(0.0).floor() as u32)
+        .map(|year| (year as f64, value_at(year as f64)))
+        .collect();
+    if req.years.fract() > 0.0 {
+        points.push((req.years, value_at(req.years)));
+    }
+    Ok(points)
+}
+
+pub fn calculate_compound(req: &CompoundRequest) -> Result<CompoundResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let final_amount = compound_growth(req)?
+        .last()
+        .map_or(req.principal, |&(_, value)| sanitize(value));
+    let interest_earned = final_amount - req.principal;
+
+    Ok(CompoundResponse {
+        final_amount: round_to(final_amount, precision),
+        interest_earned: round_to(interest_earned, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+pub fn calculate_freelance_rate(req: &FreelanceRateRequest) -> Result<FreelanceRateResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    if req.billable_hours <= 0.0 {
+        return Err("billable_hours must be greater than zero".into());
+    }
+    if req.tax_rate >= 100.0 {
+        return Err("tax_rate must be below 100".into());
+    }
+
+    let gross = gross_income(req.desired_net_income, req.tax_rate, req.business_expenses);
+    let tax_amount = gross - req.desired_net_income - req.business_expenses;
+    let hourly_rate = gross / req.billable_hours;
+
+    Ok(FreelanceRateResponse {
+        hourly_rate: round_to(hourly_rate, precision),
+        gross_income: round_to(gross, precision),
+        tax_amount: round_to(tax_amount, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+/// Working days in a week, used for the daily figure.
+const WORK_DAYS_PER_WEEK: f64 = 5.0;
+
+pub fn calculate_wage_convert(req: &WageConvertRequest) -> Result<WageConvertResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let annual_hours = req.hours_per_week * req.weeks_per_year;
+    if annual_hours <= 0.0 {
+        return Err("hours_per_week and weeks_per_year must be greater than zero".into());
+    }
+
+    let (annual, hourly) = match (req.annual_salary, req.hourly_rate) {
+        (Some(annual), None) => (annual, annual / annual_hours),
+        (None, Some(hourly)) => (hourly * annual_hours, hourly),
+        _ => return Err("Provide exactly one of `annual_salary` or `hourly_rate`".into()),
+    };
+    let weekly = hourly * req.hours_per_week;
+
+    Ok(WageConvertResponse {
+        annual: round_to(annual, precision),
+        monthly: round_to(annual / 12.0, precision),
+        weekly: round_to(weekly, precision),
+        daily: round_to(weekly / WORK_DAYS_PER_WEEK, precision),
+        hourly: round_to(hourly, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+pub fn calculate_refinance(req: &RefinanceRequest) -> RefinanceResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    // Interest over the life of a loan, from the same cent-exact schedule as credit
+    let loan = |rate: f64, years: f64| {
+        let r = rate / 100.0 / 12.0;
+        let months = (years * 12.0).round().max(0.0);
+        let pmt = annuity_payment(req.current_balance, r, months);
+        let (_, total_paid) = amortization_schedule(req.current_balance, r, months as u32, pmt, precision);
+        let interest = (total_paid - to_money(req.current_balance, precision)).to_f64().unwrap_or(0.0);
+        (pmt, interest, months)
+    };
+    let (current_payment, current_interest, current_months) = loan(req.current_rate, req.remaining_term);
+    let (new_payment, new_interest, new_months) = loan(req.new_rate, req.new_term);
+
+    let lifetime_savings = current_interest - new_interest - req.closing_costs;
+
+    // Monthly savings only accrue while both loans are still being paid
+    let monthly_savings = current_payment - new_payment;
+    let break_even_month = if monthly_savings > 0.0 {
+        let month = (req.closing_costs / monthly_savings).ceil();
+        (month <= current_months.min(new_months)).then_some(month as u32)
+    } else {
+        None
+    };
+
+    RefinanceResponse {
+        current_monthly_payment: round_to(current_payment, precision),
+        new_monthly_payment: round_to(new_payment, precision),
+        current_total_interest: round_to(current_interest, precision),
+        new_total_interest: round_to(new_interest, precision),
+        lifetime_savings: round_to(lifetime_savings, precision),
+        break_even_month,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Default and maximum number of Monte Carlo trials.
+const DEFAULT_TRIALS: u32 = 500;
+const MAX_TRIALS: u32 = 1000;
+
+/// Seed used when the request does not pick one, so results are reproducible.
+const DEFAULT_SEED: u64 = 42;
+
+/// Small seedable PRNG (SplitMix64); wasm has no OS entropy to rely on anyway.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in (0, 1].
+    fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal, via Box-Muller.
+    fn next_normal(&mut self) -> f64 {
+        let u1 = self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+    }
+}
+
+/// Savings at retirement for each trial, sorted ascending. Every year draws a
+/// normally distributed return; the year's contributions are added at its end.
+pub fn retirement_trials(req: &RetirementMonteCarloRequest) -> Vec<f64> {
+    let years = (req.retirement_age - req.current_age).round().max(0.0) as u32;
+    let trials = req.trials.unwrap_or(DEFAULT_TRIALS).clamp(1, MAX_TRIALS);
+    let mut rng = SplitMix64(req.seed.unwrap_or(DEFAULT_SEED));
+
+    let mut outcomes: Vec<f64> = (0..trials)
+        .map(|_| {
+            (0..years).fold(req.current_savings, |balance, _| {
+                let annual_return = (req.expected_return + req.volatility * rng.next_normal()) / 100.0;
+                // A return below -100% cannot take the balance below zero
+                balance * (1.0 + annual_return).max(0.0) + req.monthly_savings * 12.0
+            })
+        })
+        .collect();
+    outcomes.sort_by(f64::total_cmp);
+    outcomes
+}
+
+/// Nearest-rank percentile of sorted `values`.
+fn percentile(values: &[f64], p: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values[((values.len() - 1) as f64 * p / 100.0).round() as usize]
+}
+
+pub fn calculate_retirement_monte_carlo(req: &RetirementMonteCarloRequest) -> RetirementMonteCarloResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let outcomes = retirement_trials(req);
+    let required_capital = retirement_capital(req.desired_income, DEFAULT_WITHDRAWAL_RATE);
+    let successes = outcomes.iter().filter(|&&v| v >= required_capital).count();
+    let probability = successes as f64 / outcomes.len().max(1) as f64 * 100.0;
+
+    RetirementMonteCarloResponse {
+        success_probability: round_to(probability, 1),
+        required_capital: round_to(required_capital, precision),
+        percentile_10: round_to(percentile(&outcomes, 10.0), precision),
+        percentile_50: round_to(percentile(&outcomes, 50.0), precision),
+        percentile_90: round_to(percentile(&outcomes, 90.0), precision),
+        trials: outcomes.len() as u32,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Compound growth of a purchase price left invested, compounded yearly.
+pub fn calculate_opportunity_cost(req: &OpportunityCostRequest) -> OpportunityCostResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let future_value = sanitize(req.amount * (1.0 + req.annual_return / 100.0).powf(req.years));
+    let lost_gain = future_value - req.amount;
+
+    OpportunityCostResponse {
+        future_value: round_to(future_value, precision),
+        lost_gain: round_to(lost_gain, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Months until `saved`, growing at monthly rate `r` plus `contribution` at the
+/// end of each month, reaches `target`; -1.0 when it never does.
+fn months_to_goal(target: f64, saved: f64, contribution: f64, r: f64) -> f64 {
+    if r == 0.0 || saved >= target {
+        return months_to_save(target, saved, contribution);
+    }
+    // Solves saved * (1+r)^n + contribution * ((1+r)^n - 1) / r = target for n
+    let months = ((target * r + contribution) / (saved * r + contribution)).ln() / (1.0 + r).ln();
+    if months.is_finite() && months >= 0.0 { months } else { -1.0 }
+}
+
+pub fn calculate_goal_progress(req: &GoalProgressRequest) -> GoalProgressResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let percent_complete = if req.target_amount > 0.0 {
+        (req.current_amount / req.target_amount * 100.0).min(100.0)
+    } else {
+        100.0
+    };
+    let remaining = (req.target_amount - req.current_amount).max(0.0);
+    let r = req.annual_return.unwrap_or(0.0) / 100.0 / 12.0;
+    let months_remaining = months_to_goal(req.target_amount, req.current_amount, req.monthly_contribution, r);
+
+    GoalProgressResponse {
+        percent_complete: round_to(percent_complete, 1),
+        remaining_amount: round_to(remaining, precision),
+        months_remaining: round_to(months_remaining, 1),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+pub fn calculate_subscription(req: &SubscriptionRequest) -> Result<SubscriptionResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    if req.uses_per_month <= 0.0 {
+        return Err("uses_per_month must be greater than zero".into());
+    }
+
+    let cost_per_use = req.monthly_cost / req.uses_per_month;
+    let pay_per_use_cost = req.per_use_price.map(|price| price * req.uses_per_month);
+    let monthly_savings = pay_per_use_cost.map(|cost| cost - req.monthly_cost);
+
+    Ok(SubscriptionResponse {
+        cost_per_use: round_to(cost_per_use, precision),
+        pay_per_use_cost: pay_per_use_cost.map(|cost| round_to(cost, precision)),
+        monthly_savings: monthly_savings.map(|savings| round_to(savings, precision)),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+pub fn calculate_wage_erosion(req: &WageErosionRequest) -> WageErosionResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let raise = 1.0 + req.annual_raise / 100.0;
+    let inflation = 1.0 + req.inflation_rate / 100.0;
+    let years: Vec<WageErosionYear> = (0..=req.horizon.max(0.0).floor() as u32)
+        .map(|year| {
+            let nominal = req.current_salary * raise.powi(year as i32);
+            WageErosionYear {
+                year,
+                nominal_salary: round_to(nominal, precision),
+                real_salary: round_to(sanitize(nominal / inflation.powi(year as i32)), precision),
+            }
+        })
+        .collect();
+
+    let final_real_salary = years.last().map_or(req.current_salary, |y| y.real_salary);
+    let real_change = if req.current_salary > 0.0 {
+        (final_real_salary / req.current_salary - 1.0) * 100.0
+    } else {
+        0.0
+    };
+    let real_trend = if real_change > 0.0 {
+        "grows"
+    } else if real_change < 0.0 {
+        "shrinks"
+    } else {
+        "flat"
+    };
+
+    WageErosionResponse {
+        years,
+        final_real_salary,
+        real_change: round_to(real_change, 2),
+        real_trend: real_trend.to_string(),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+pub fn calculate_net_pay(req: &NetPayRequest) -> NetPayResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let income_tax = round_to(req.gross_salary * req.income_tax_rate / 100.0, precision);
+    let social_contributions = round_to(req.gross_salary * req.social_contribution_rate / 100.0, precision);
+    let fixed_deductions = round_to(req.fixed_deductions.unwrap_or(0.0), precision);
+    // From the rounded parts, so the breakdown adds up to the gross salary exactly
+    let net_pay = req.gross_salary - income_tax - social_contributions - fixed_deductions;
+
+    NetPayResponse {
+        net_pay: round_to(net_pay, precision),
+        income_tax,
+        social_contributions,
+        fixed_deductions,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Inverse of the retirement projection: the monthly savings that, compounded
+/// monthly alongside the current savings, reach the target after `years`.
+pub fn calculate_required_savings(req: &RequiredSavingsRequest) -> RequiredSavingsResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let r = req.annual_return / 100.0 / 12.0;
+    let n = (req.years * 12.0).round().max(1.0) as i32;
+    let current = req.current_savings.unwrap_or(0.0);
+
+    // Solves target = current * (1+r)^n + payment * ((1+r)^n - 1) / r for the payment
+    let missing = req.target_amount - current * (1.0 + r).powi(n);
+    let payment = if r != 0.0 {
+        missing * r / ((1.0 + r).powi(n) - 1.0)
+    } else {
+        missing / n as f64
+    };
+    let monthly_savings = sanitize(payment).max(0.0);
+
+    let total_contributions = current + monthly_savings * n as f64;
+    let final_amount = future_value(current, monthly_savings, r, n);
+
+    RequiredSavingsResponse {
+        monthly_savings: round_to(monthly_savings, precision),
+        total_contributions: round_to(total_contributions, precision),
+        total_growth: round_to(sanitize(final_amount) - total_contributions, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+pub fn calculate_debt_vs_invest(req: &DebtVsInvestRequest) -> DebtVsInvestResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let debt_r = req.debt_rate / 100.0 / 12.0;
+    let invest_r = req.investment_return / 100.0 / 12.0;
+    let months = (req.horizon * 12.0).round() as u32;
+
+    // Both choices spend the same monthly amount, so their net worth compares directly
+    let (mut debt_first, mut invested_first) = (req.debt_balance, 0.0);
+    let (mut debt_kept, mut invested_all) = (req.debt_balance, 0.0);
+    let mut months_to_debt_free = if req.debt_balance > 0.0 { -1.0 } else { 0.0 };
+    for month in 1..=months {
+        debt_first *= 1.0 + debt_r;
+        let payment = req.monthly_amount.min(debt_first);
+        debt_first -= payment;
+        invested_first = invested_first * (1.0 + invest_r) + req.monthly_amount - payment;
+        if debt_first <= 0.0 && months_to_debt_free < 0.0 {
+            months_to_debt_free = month as f64;
+        }
+
+        debt_kept *= 1.0 + debt_r;
+        invested_all = invested_all * (1.0 + invest_r) + req.monthly_amount;
+    }
+
+    let outcome = |investments: f64, debt: f64| DebtVsInvestOutcome {
+        investments: round_to(sanitize(investments), precision),
+        remaining_debt: round_to(sanitize(debt), precision),
+        net_worth: round_to(sanitize(investments - debt), precision),
+    };
+    let pay_debt = outcome(invested_first, debt_first);
+    let invest = outcome(invested_all, debt_kept);
+    // A tie goes to paying the debt, its return is guaranteed
+    let (recommendation, advantage) = if pay_debt.net_worth >= invest.net_worth {
+        ("pay_debt", pay_debt.net_worth - invest.net_worth)
+    } else {
+        ("invest", invest.net_worth - pay_debt.net_worth)
+    };
+
+    DebtVsInvestResponse {
+        recommendation: recommendation.to_string(),
+        advantage: round_to(advantage, precision),
+        months_to_debt_free,
+        pay_debt,
+        invest,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Lump sum invested today against the same amount split into equal monthly
+/// installments, the first also invested today, both held until the horizon.
+pub fn calculate_lump_vs_dca(req: &LumpVsDcaRequest) -> LumpVsDcaResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let r = req.annual_return / 100.0 / 12.0;
+    let n = (req.horizon * 12.0).round() as i32;
+    let periods = req.dca_periods as i32;
+
+    let lump_sum_value = future_value(req.total_amount, 0.0, r, n);
+    // Installments at the start of each month: an annuity due, then left to grow
+    let installment = req.total_amount / periods as f64;
+    let dca_value = future_value(0.0, installment, r, periods) * (1.0 + r) * (1.0 + r).powi(n - periods);
+
+    LumpVsDcaResponse {
+        lump_sum_value: round_to(sanitize(lump_sum_value), precision),
+        dca_value: round_to(sanitize(dca_value), precision),
+        difference: round_to(sanitize(lump_sum_value - dca_value), precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Value of the amount after each year of inflation, compounded yearly: rising
+/// towards the future, or shrinking into the past.
+pub fn inflation_path(req: &InflationRequest) -> Result<Vec<(f64, f64)>, String> {
+    let growth = 1.0 + req.inflation_rate / 100.0;
+    let factor = match req.direction.as_str() {
+        "future" => growth,
+        "past" => 1.0 / growth,
+        other => return Err(format!("Unknown direction: {} (expected future or past)", other)),
+    };
+    let value_at = |years: f64| req.amount * factor.powf(years);
+
+    let mut points: Vec<(f64, f64)> = (0..=req.years.floor() as u32)
+        .map(|year| (year as f64, value_at(year as f64)))
+        .collect();
+    if req.years.fract() > 0.0 {
+        points.push((req.years, value_at(req.years)));
+    }
+    Ok(points)
+}
+
+pub fn calculate_inflation(req: &InflationRequest) -> Result<InflationResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let adjusted_amount = inflation_path(req)?
+        .last()
+        .map_or(req.amount, |&(_, value)| sanitize(value));
+    let total_change = if req.amount > 0.0 {
+        (adjusted_amount / req.amount - 1.0) * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(InflationResponse {
+        adjusted_amount: round_to(adjusted_amount, precision),
+        total_change: round_to(sanitize(total_change), 2),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+/// Runs one offer through the hourly income calculator.
+fn job_offer_result(name: &str, offer: &JobOffer, req: &JobCompareRequest) -> Result<JobOfferResult, String> {
+    let hourly = calculate_hourly_income(&HourlyIncomeRequest {
+        monthly_income: offer.monthly_income,
+        taxes: offer.taxes,
+        work_hours: offer.work_hours,
+        commute_time: offer.commute_time,
+        work_expenses: offer.work_expenses,
+        options: CommonOptions {
+            currency: req.options.currency.clone(),
+            precision: req.options.precision,
+            ..Default::default()
+        },
+    })
+    .map_err(|e| format!("{}.{}", name, e))?;
+
+    Ok(JobOfferResult {
+        real_hourly_income: hourly.real_hourly_income,
+        nominal_hourly_income: hourly.nominal_hourly_income,
+        net_income: hourly.net_income,
+        efficiency: hourly.efficiency,
+    })
+}
+
+pub fn calculate_job_compare(req: &JobCompareRequest) -> Result<JobCompareResponse, String> {
+    let offer_a = job_offer_result("offer_a", &req.offer_a, req)?;
+    let offer_b = job_offer_result("offer_b", &req.offer_b, req)?;
+
+    let (a, b) = (offer_a.real_hourly_income, offer_b.real_hourly_income);
+    let (better_offer, better, worse) = if a > b {
+        ("a", a, b)
+    } else if b > a {
+        ("b", b, a)
+    } else {
+        ("equal", a, b)
+    };
+
+    Ok(JobCompareResponse {
+        offer_a,
+        offer_b,
+        better_offer: better_offer.to_string(),
+        real_rate_difference: round_to(sanitize((better - worse) / worse.abs() * 100.0), 1),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+fn occurrences_per_year(frequency: &str) -> Result<f64, String> {
+    match frequency {
+        "daily" => Ok(365.0),
+        "weekly" => Ok(52.0),
+        "monthly" => Ok(12.0),
+        other => Err(format!("Unknown frequency: {} (expected daily, weekly or monthly)", other)),
+    }
+}
+
+/// A fixed monthly amount put aside so far and what it grows into invested at
+/// the end of each month, after each year and at `years` itself.
+fn monthly_savings_growth(monthly: f64, annual_return: f64, years: f64) -> Vec<(f64, f64, f64)> {
+    let r = annual_return / 100.0 / 12.0;
+    let point = |years: f64| {
+        let months = (years * 12.0).round();
+        (years, monthly * months, future_value(0.0, monthly, r, months as i32))
+    };
+
+    let mut points: Vec<(f64, f64, f64)> = (0..=years.floor() as u32).map(|year| point(year as f64)).collect();
+    if years.fract() > 0.0 {
+        points.push(point(years));
+    }
+    points
+}
+
+/// Money spent so far and what it would have grown into, after each year.
+/// The spending is invested monthly, at the end of each month.
+pub fn recurring_cost_growth(req: &RecurringCostRequest) -> Result<Vec<(f64, f64, f64)>, String> {
+    let monthly = req.cost * occurrences_per_year(&req.frequency)? / 12.0;
+    Ok(monthly_savings_growth(monthly, req.annual_return.unwrap_or(0.0), req.years))
+}
+
+pub fn calculate_recurring_cost(req: &RecurringCostRequest) -> Result<RecurringCostResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let annual_cost = req.cost * occurrences_per_year(&req.frequency)?;
+    let (total_cost, future_value) = recurring_cost_growth(req)?
+        .last()
+        .map_or((0.0, 0.0), |&(_, spent, invested)| (spent, sanitize(invested)));
+
+    Ok(RecurringCostResponse {
+        annual_cost: round_to(annual_cost, precision),
+        total_cost: round_to(total_cost, precision),
+        future_value: round_to(future_value, precision),
+        lost_gain: round_to(future_value - total_cost, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+pub fn calculate_blended_rate(req: &BlendedRateRequest) -> BlendedRateResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let total_balance: f64 = req.debts.iter().map(|d| d.balance).sum();
+    let weighted: f64 = req.debts.iter().map(|d| d.balance * d.rate).sum();
+    let blended_rate = if total_balance > 0.0 { weighted / total_balance } else { 0.0 };
+
+    BlendedRateResponse {
+        total_balance: round_to(total_balance, precision),
+        blended_rate: round_to(blended_rate, 2),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+pub fn calculate_savings_compare(req: &SavingsCompareRequest) -> SavingsCompareResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    // Deposits go in monthly, so the APY is turned into the monthly rate that compounds to it
+    let months = (req.years * 12.0) as i32;
+    let total_deposited = req.principal + req.monthly_deposit * months as f64;
+    let mut accounts: Vec<SavingsAccountResult> = req
+        .accounts
+        .iter()
+        .map(|account| {
+            let r = (1.0 + account.apy / 100.0).powf(1.0 / 12.0) - 1.0;
+            let balance = future_value(req.principal, req.monthly_deposit, r, months);
+            SavingsAccountResult {
+                name: account.name.clone(),
+                apy: account.apy,
+                final_balance: round_to(balance, precision),
+                interest_earned: round_to(balance - total_deposited, precision),
+                rank: 0,
+            }
+        })
+        .collect();
+
+    // Stable sort, so equal balances keep the order they were sent in
+    accounts.sort_by(|a, b| b.final_balance.total_cmp(&a.final_balance));
+    for (i, account) in accounts.iter_mut().enumerate() {
+        account.rank = i as u32 + 1;
+    }
+
+    SavingsCompareResponse {
+        best_balance: accounts.first().map_or(0.0, |a| a.final_balance),
+        best_account: accounts.first().map(|a| a.name.clone()).unwrap_or_default(),
+        total_deposited: round_to(total_deposited, precision),
+        accounts,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Round-ups put aside so far and what they grow into invested, after each year.
+pub fn round_up_growth(req: &RoundUpRequest) -> Vec<(f64, f64, f64)> {
+    let monthly = req.transactions_per_month * req.round_up;
+    monthly_savings_growth(monthly, req.annual_return.unwrap_or(0.0), req.years)
+}
+
+pub fn calculate_round_up(req: &RoundUpRequest) -> RoundUpResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let monthly_savings = req.transactions_per_month * req.round_up;
+    let (total_saved, future_value) = round_up_growth(req)
+        .last()
+        .map_or((0.0, 0.0), |&(_, saved, invested)| (saved, sanitize(invested)));
+
+    RoundUpResponse {
+        monthly_savings: round_to(monthly_savings, precision),
+        annual_savings: round_to(monthly_savings * 12.0, precision),
+        total_saved: round_to(total_saved, precision),
+        future_value: round_to(future_value, precision),
+        investment_gain: round_to(future_value - total_saved, precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+pub fn calculate_affordability(req: &AffordabilityRequest) -> AffordabilityResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    // Everything owed monthly, the new mortgage included, stays within the DTI limit
+    let max_payment = (req.monthly_income * req.dti_ratio / 100.0 - req.monthly_debt_payments).max(0.0);
+    let r = req.mortgage_rate / 100.0 / 12.0;
+    let max_loan = annuity_principal(max_payment, r, req.term * 12.0);
+    let debt_to_income = if req.monthly_income > 0.0 {
+        (req.monthly_debt_payments + max_payment) / req.monthly_income * 100.0
+    } else {
+        0.0
+    };
+
+    AffordabilityResponse {
+        max_monthly_payment: round_to(max_payment, precision),
+        max_loan_amount: round_to(sanitize(max_loan), precision),
+        debt_to_income: round_to(debt_to_income, 1),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+pub fn calculate_bill_split(req: &BillSplitRequest) -> BillSplitResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let bill = to_money(req.bill_total, precision);
+    let tip = cents(bill * Decimal::from_f64(req.tip_percent).unwrap_or_default() / Decimal::ONE_HUNDRED, precision);
+    let total = bill + tip;
+
+    // Split in the currency's smallest unit; what doesn't divide evenly is one
+    // unit more for the first few people, so the shares add up to the total
+    let people = req.people.max(1);
+    let unit = Decimal::new(1, precision);
+    let base = (total / Decimal::from(people) / unit).floor() * unit;
+    let remainder = ((total - base * Decimal::from(people)) / unit).to_u32().unwrap_or(0);
+    let shares: Vec<f64> = (0..people)
+        .map(|i| if i < remainder { base + unit } else { base })
+        .map(|share| share.to_f64().unwrap_or(0.0))
+        .collect();
+
+    BillSplitResponse {
+        per_person: shares.first().copied().unwrap_or(0.0),
+        shares,
+        total_tip: tip.to_f64().unwrap_or(0.0),
+        grand_total: total.to_f64().unwrap_or(0.0),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Yearly raise in money from whichever of `raise_amount` and `raise_percent` was given.
+fn raise_amount(req: &RaiseImpactRequest) -> Result<f64, String> {
+    match (req.raise_amount, req.raise_percent) {
+        (Some(amount), None) => Ok(amount),
+        (None, Some(percent)) => Ok(req.current_salary * percent / 100.0),
+        _ => Err("Provide exactly one of `raise_amount` or `raise_percent`".into()),
+    }
+}
+
+/// Salary in each working year (1 to `years`) without and with the raise.
+pub fn raise_trajectories(req: &RaiseImpactRequest) -> Result<Vec<(f64, f64, f64)>, String> {
+    let raise = raise_amount(req)?;
+    let growth = 1.0 + req.annual_raise / 100.0;
+    Ok((0..req.years.max(1.0).floor() as i32)
+        .map(|year| {
+            let factor = growth.powi(year);
+            ((year + 1) as f64, req.current_salary * factor, (req.current_salary + raise) * factor)
+        })
+        .collect())
+}
+
+pub fn calculate_raise_impact(req: &RaiseImpactRequest) -> Result<RaiseImpactResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let raise = raise_amount(req)?;
+    let years = req.years.max(1.0).floor() as i32;
+    let growth = req.annual_raise / 100.0;
+    // The raise grows with every later raise, so the extra pay is an annuity growing at that rate
+    let lifetime_difference = future_value(0.0, raise, growth, years);
+    let trajectories = raise_trajectories(req)?;
+    let (_, final_without, final_with) = trajectories.last().copied().unwrap_or((1.0, req.current_salary, req.current_salary + raise));
+
+    // Each year's extra pay is invested at its end and compounds until retirement
+    let invested_difference = req.invest_return.map(|rate| {
+        let rate = rate / 100.0;
+        trajectories.iter().fold(0.0, |balance, &(_, without, with)| balance * (1.0 + rate) + (with - without))
+    });
+
+    Ok(RaiseImpactResponse {
+        raise_amount: round_to(raise, precision),
+        final_salary_with_raise: round_to(final_with, precision),
+        final_salary_without_raise: round_to(final_without, precision),
+        lifetime_difference: round_to(sanitize(lifetime_difference), precision),
+        invested_difference: invested_difference.map(|value| round_to(sanitize(value), precision)),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+/// The 50/30/20 rule: needs, wants and savings as percentages of take-home pay.
+const DEFAULT_BUDGET_SPLIT: [f64; 3] = [50.0, 30.0, 20.0];
+
+pub fn calculate_budget(req: &BudgetRequest) -> Result<BudgetResponse, String> {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let [needs, wants, savings] = DEFAULT_BUDGET_SPLIT;
+    let split = [req.needs_percent.unwrap_or(needs), req.wants_percent.unwrap_or(wants), req.savings_percent.unwrap_or(savings)];
+    let total: f64 = split.iter().sum();
+    // Tolerates the float noise of shares like 33.33 + 33.33 + 33.34
+    if (total - 100.0).abs() > 1e-6 {
+        return Err(format!("needs_percent, wants_percent and savings_percent must add up to 100 (got {})", total));
+    }
+    let [needs, wants, savings] = split.map(|percent| round_to(req.monthly_income * percent / 100.0, precision));
+
+    Ok(BudgetResponse {
+        needs,
+        wants,
+        savings,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+pub fn calculate_cost_of_waiting(req: &CostOfWaitingRequest) -> CostOfWaitingResponse {
+    let precision = money_precision(req.options.precision, &req.options.currency);
+
+    let r = req.annual_return / 100.0 / 12.0;
+    let months = (req.years * 12.0).round() as i32;
+    let delayed_months = ((req.years - req.delay) * 12.0).round().max(0.0) as i32;
+    let start_now_value = future_value(0.0, req.monthly_contribution, r, months);
+    let start_later_value = future_value(0.0, req.monthly_contribution, r, delayed_months);
+
+    CostOfWaitingResponse {
+        start_now_value: round_to(sanitize(start_now_value), precision),
+        start_later_value: round_to(sanitize(start_later_value), precision),
+        cost_of_waiting: round_to(sanitize(start_now_value - start_later_value), precision),
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    }
+}
+
+/// Runs one term through the credit calculator.
+fn loan_term_result(term: f64, req: &LoanTermsRequest) -> Result<LoanTermResult, String> {
+    let credit = calculate_credit(&CreditRequest {
+        amount: req.amount,
+        rate: req.rate,
+        term,
+        payment_frequency: None,
+        include_schedule: None,
+        chart_type: None,
+        options: CommonOptions {
+            currency: req.options.currency.clone(),
+            precision: req.options.precision,
+            ..Default::default()
+        },
+    })?;
+
+    Ok(LoanTermResult {
+        term,
+        monthly_payment: credit.monthly_payment,
+        total_interest: credit.overpayment,
+        total_paid: credit.total_payment,
+    })
+}
+
+pub fn calculate_loan_terms(req: &LoanTermsRequest) -> Result<LoanTermsResponse, String> {
+    let terms = req
+        .terms
+        .iter()
+        .map(|&term| loan_term_result(term, req))
+        .collect::<Result<Vec<_>, _>>()?;
+    let lowest = |value: fn(&LoanTermResult) -> f64| terms.iter().map(value).fold(f64::INFINITY, f64::min);
+    let lowest_monthly_payment = lowest(|t| t.monthly_payment);
+    let lowest_total_interest = lowest(|t| t.total_interest);
+
+    Ok(LoanTermsResponse {
+        terms,
+        lowest_monthly_payment,
+        lowest_total_interest,
+        currency_symbol: get_currency_symbol(&req.options.currency),
+        chart: String::new(),
+    })
+}
+
+// Known-good results worked out by hand (or, for the month-by-month
+// simulations, with an independent script), compared with a small tolerance
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const EPSILON: f64 = 0.01;
+
+    /// Builds a request from its JSON body, the way the router does.
+    fn request<T: serde::de::DeserializeOwned>(body: serde_json::Value) -> T {
+        serde_json::from_value(body).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= EPSILON, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn hourly_income() {
+        // Net 3000 * 0.8 - 200 = 2200 over 160 + 40 hours; nominal 3000 / 160
+        let res = calculate_hourly_income(&request(json!({
+            "monthly_income": 3000, "taxes": 20, "work_hours": 160, "commute_time": 40, "work_expenses": 200, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_close(res.net_income, 2200.0);
+        assert_close(res.real_hourly_income, 11.0);
+        assert_close(res.nominal_hourly_income, 18.75);
+        assert_close(res.efficiency, 58.7);
+    }
+
+    #[test]
+    fn time_value() {
+        // 36000 / 2000 hours, times the PPP factor
+        let res = calculate_time_value(&request(json!({
+            "annual_income": 36000, "annual_hours": 2000, "ppp_factor": 1.5, "currency": "USD"
+        })))
+        .unwrap();
+        assert_close(res.time_value, 18.0);
+        assert_close(res.ppp_adjusted_value, 27.0);
+    }
+
+    #[test]
+    fn investment() {
+        // 1000 * 1.12^10 + 1200 * (1.12^10 - 1) / 0.12
+        let res = calculate_investment(&request(json!({
+            "initial_amount": 1000, "monthly_contribution": 100, "annual_return": 12, "period": 10, "compounding": "annual", "currency": "EUR"
+        })))
+        .unwrap();
+        assert_close(res.future_value, 24164.33);
+        assert_close(res.total_contributions, 13000.0);
+        assert_close(res.total_gain, 11164.33);
+        assert_close(res.roi, 85.9);
+    }
+
+    #[test]
+    fn investment_annuity_due() {
+        // Paid at the start of each year, every contribution grows once more: the annuity part times 1.12
+        let due = calculate_investment(&request(json!({
+            "initial_amount": 1000, "monthly_contribution": 100, "annual_return": 12, "period": 10, "compounding": "annual",
+            "annuity_due": true, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_close(due.future_value, 26691.35);
+        assert_close(due.total_contributions, 13000.0);
+        assert_close(due.total_gain, 13691.35);
+
+        let ordinary = calculate_investment(&request(json!({
+            "initial_amount": 1000, "monthly_contribution": 100, "annual_return": 12, "period": 10, "compounding": "annual",
+            "currency": "EUR"
+        })))
+        .unwrap();
+        assert!(due.future_value > ordinary.future_value);
+    }
+
+    #[test]
+    fn credit() {
+        // Annuity at 1% a month over 36 months; 35 payments of 166.07 and a last one settling the rest
+        let res = calculate_credit(&request(json!({"amount": 5000, "rate": 12, "term": 3, "currency": "USD"}))).unwrap();
+        assert_close(res.monthly_payment, 166.07);
+        assert_close(res.total_payment, 5978.60);
+        assert_close(res.overpayment, 978.60);
+        assert_close(res.effective_annual_rate, 12.68);
+        assert_close(res.first_month_interest, 50.0);
+        assert_close(res.first_month_principal, 116.07);
+    }
+
+    #[test]
+    fn retirement() {
+        // 10000 * (1 + 0.07/12)^360 + 500 * annuity factor; 2000 * 12 / 4% = 600000 needed
+        let res = calculate_retirement(&request(json!({
+            "current_age": 30, "retirement_age": 60, "desired_income": 2000, "current_savings": 10000,
+            "monthly_savings": 500, "expected_return": 7, "currency": "EUR"
+        })));
+        assert_close(res.future_value_savings, 81164.97);
+        assert_close(res.future_value_contributions, 609985.50);
+        assert_close(res.future_value, 691150.47);
+        assert_close(res.required_capital, 600000.0);
+        assert_close(res.gap, 0.0);
+    }
+
+    #[test]
+    fn retirement_annuity_due() {
+        // Saving at the start of each month: the contributions times 1 + 0.07/12, the savings unchanged
+        let res = calculate_retirement(&request(json!({
+            "current_age": 30, "retirement_age": 60, "desired_income": 2000, "current_savings": 10000,
+            "monthly_savings": 500, "expected_return": 7, "annuity_due": true, "currency": "EUR"
+        })));
+        assert_close(res.future_value_savings, 81164.97);
+        assert_close(res.future_value_contributions, 613543.75);
+        assert_close(res.future_value, 694708.72);
+    }
+
+    #[test]
+    fn debt_payoff() {
+        // ln(200 / (200 - 75)) / ln(1.015) = 31.57 months, the last payment only settles the rest
+        let res = calculate_debt_payoff(&request(json!({
+            "balance": 5000, "interest_rate": 18, "monthly_payment": 200, "extra_payment": 0, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_eq!(res.months, 32);
+        assert_close(res.total_paid, 6313.97);
+        assert_close(res.total_interest, 1313.97);
+    }
+
+    #[test]
+    fn mortgage_totals_are_cent_exact() {
+        // 200000 at 6% over 30 years, the interest rounded to the cent every month
+        let res = calculate_credit(&request(json!({"amount": 200000, "rate": 6, "term": 30, "currency": "USD"}))).unwrap();
+        assert_eq!(res.monthly_payment, 1199.10);
+        assert_eq!(res.total_payment, 431677.04);
+        assert_eq!(res.overpayment, 231677.04);
+
+        // Paying the rounded-down 1199.10 as a debt leaves a few cents for a 361st month
+        let res = calculate_debt_payoff(&request(json!({
+            "balance": 200000, "interest_rate": 6, "monthly_payment": 1199.10, "extra_payment": 0, "currency": "USD"
+        })))
+        .unwrap();
+        assert_eq!(res.months, 361);
+        assert_eq!(res.total_paid, 431677.05);
+        assert_eq!(res.total_interest, 231677.05);
+    }
+
+    #[test]
+    fn debt_payoff_is_capped() {
+        // A cent above the interest takes far longer than 100 years
+        let err = calculate_debt_payoff(&request(json!({
+            "balance": 10000, "interest_rate": 1.2, "monthly_payment": 10.01, "extra_payment": 0, "currency": "USD"
+        })))
+        .err()
+        .unwrap();
+        assert!(err.starts_with("monthly_payment must pay off the balance"), "{}", err);
+    }
+
+    #[test]
+    fn debt_payoff_interest_only_boundary() {
+        // 1% a month on 10000: exactly 100 services the debt without shrinking it
+        assert_eq!(min_viable_payment(10000.0, 0.01), 100.0);
+        let res = calculate_debt_payoff(&request(json!({
+            "balance": 10000, "interest_rate": 12, "monthly_payment": 100, "extra_payment": 0, "currency": "USD"
+        })))
+        .unwrap();
+        assert!(!res.payable);
+        assert_eq!(res.minimum_payment_required, Some(100.01));
+
+        // A cent less would grow the balance
+        let err = calculate_debt_payoff(&request(json!({
+            "balance": 10000, "interest_rate": 12, "monthly_payment": 99.99, "extra_payment": 0, "currency": "USD"
+        })))
+        .err()
+        .unwrap();
+        assert!(err.starts_with("monthly_payment must be at least"), "{}", err);
+
+        // The same check, with the same wording, for the debt strategies
+        let err = calculate_debt_strategy(&request(json!({
+            "debts": [{"balance": 10000, "rate": 24, "min_payment": 100}], "extra_payment": 0, "currency": "EUR"
+        })))
+        .err()
+        .unwrap();
+        assert_eq!(err, "min_payment must be at least the monthly interest of 200");
+    }
+
+    #[test]
+    fn debt_strategy() {
+        // Without interest both orders take ceil(1500 / 200) months
+        let res = calculate_debt_strategy(&request(json!({
+            "debts": [{"balance": 1000, "rate": 0, "min_payment": 50}, {"balance": 500, "rate": 0, "min_payment": 50}],
+            "extra_payment": 100, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_eq!(res.snowball.months, 8);
+        assert_eq!(res.avalanche.months, 8);
+        assert_close(res.interest_saved, 0.0);
+
+        let res = calculate_debt_strategy(&request(json!({
+            "debts": [{"balance": 2000, "rate": 24, "min_payment": 50}, {"balance": 500, "rate": 6, "min_payment": 25}],
+            "extra_payment": 200, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_eq!(res.snowball.months, 11);
+        assert_close(res.snowball.total_interest, 271.97);
+        assert_eq!(res.avalanche.months, 10);
+        assert_close(res.avalanche.total_interest, 221.16);
+        assert_close(res.interest_saved, 50.81);
+    }
+
+    #[test]
+    fn emergency_fund() {
+        // 1500 * 6 = 9000, 7000 short, 200 a month
+        let res = calculate_emergency_fund(&request(json!({
+            "monthly_expenses": 1500, "months_coverage": 6, "current_savings": 2000, "monthly_contribution": 200, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_close(res.target_amount, 9000.0);
+        assert_close(res.remaining_amount, 7000.0);
+        assert_close(res.months_to_target, 35.0);
+        assert_close(res.current_coverage_months.unwrap(), 1.3);
+    }
+
+    #[test]
+    fn tax() {
+        let res = calculate_tax(&request(json!({"income": 50000, "tax_rate": 18, "currency": "UAH"})));
+        assert_close(res.tax_amount, 9000.0);
+        assert_close(res.net_income, 41000.0);
+        assert_close(res.effective_rate, 18.0);
+        assert_close(res.marginal_rate, 18.0);
+
+        // 10% of the first 10000 above the allowance, 20% of the remaining 30000
+        let res = calculate_tax(&request(json!({
+            "income": 50000, "tax_rate": 0, "allowance": 10000,
+            "brackets": [{"up_to": 10000, "rate": 10}, {"rate": 20}], "currency": "UAH"
+        })));
+        assert_close(res.tax_amount, 7000.0);
+        assert_close(res.net_income, 43000.0);
+        assert_close(res.effective_rate, 14.0);
+        assert_close(res.marginal_rate, 20.0);
+
+        // Income past a finite last band is still taxed at the last rate
+        let res = calculate_tax(&request(json!({
+            "income": 60000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": 50000, "rate": 20}],
+            "currency": "UAH"
+        })));
+        assert_close(res.tax_amount, 10000.0);
+        assert_close(res.marginal_rate, 20.0);
+
+        // 10% of 20000, 20% of the next 30000, 30% of the rest
+        let res = calculate_tax(&request(json!({
+            "income": 60000, "tax_rate": 0,
+            "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": 50000, "rate": 20}, {"up_to": null, "rate": 30}],
+            "currency": "UAH"
+        })));
+        assert_close(res.tax_amount, 11000.0);
+        assert_close(res.effective_rate, 18.3);
+
+        // Nothing to tax, and no division by zero in the effective rate
+        let res = calculate_tax(&request(json!({
+            "income": 0, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"rate": 20}], "currency": "UAH"
+        })));
+        assert_eq!(res.tax_amount, 0.0);
+        assert_eq!(res.effective_rate, 0.0);
+        assert_eq!(res.marginal_rate, 0.0);
+    }
+
+    #[test]
+    fn btc_keeps_satoshis() {
+        // 18% of 0.12345678 is 0.0222222204, rounded to whole satoshis
+        let res = calculate_tax(&request(json!({"income": 0.12345678, "tax_rate": 18, "currency": "BTC"})));
+        assert_eq!(res.tax_amount, 0.02222222);
+        assert_eq!(res.net_income, 0.10123456);
+
+        // An explicit precision wins over the currency's own
+        let res = calculate_tax(&request(json!({"income": 0.12345678, "tax_rate": 18, "currency": "BTC", "precision": 2})));
+        assert_eq!(res.tax_amount, 0.02);
+    }
+
+    #[test]
+    fn buy_rent() {
+        // Paid in cash: a year of 1% upkeep against 6000 of rent and the cash invested at 7%
+        let res = calculate_buy_rent(&request(json!({
+            "property_price": 100000, "down_payment": 100000, "mortgage_rate": 5, "mortgage_term": 20, "monthly_rent": 500,
+            "rent_growth": 0, "property_growth": 0, "horizon": 1, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_close(res.net_buy_position, -1000.0);
+        assert_close(res.net_rent_position, 101000.0);
+        assert_close(res.total_interest_paid, 0.0);
+    }
+
+    #[test]
+    fn raise_impact() {
+        // A 5% raise on 50000 is 2500 a year, growing 3% a year for 30 years: 2500 * (1.03^30 - 1) / 0.03,
+        // and invested at 7% every year it more than doubles again
+        let res = calculate_raise_impact(&request(json!({
+            "current_salary": 50000, "raise_percent": 5, "annual_raise": 3, "years": 30, "invest_return": 7, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_close(res.raise_amount, 2500.0);
+        assert_close(res.lifetime_difference, 118938.54);
+        assert_close(res.invested_difference.unwrap(), 324062.04);
+        assert_close(res.final_salary_with_raise, 123719.69);
+    }
+
+    #[test]
+    fn budget() {
+        // 50/30/20 of 3000 by default; custom shares replace only the ones given
+        let res = calculate_budget(&request(json!({"monthly_income": 3000, "currency": "EUR"}))).unwrap();
+        assert_close(res.needs, 1500.0);
+        assert_close(res.wants, 900.0);
+        assert_close(res.savings, 600.0);
+
+        let res = calculate_budget(&request(json!({
+            "monthly_income": 3000, "needs_percent": 60, "wants_percent": 20, "currency": "EUR"
+        })))
+        .unwrap();
+        assert_close(res.needs, 1800.0);
+        assert_close(res.wants, 600.0);
+        assert_close(res.savings, 600.0);
+    }
+
+    #[test]
+    fn cost_of_waiting() {
+        // 500 a month at 7% / 12 for 360 months against 300: waiting 5 years costs a third of the result
+        let res = calculate_cost_of_waiting(&request(json!({
+            "monthly_contribution": 500, "annual_return": 7, "years": 30, "delay": 5, "currency": "EUR"
+        })));
+        assert_close(res.start_now_value, 609985.50);
+        assert_close(res.start_later_value, 405035.85);
+        assert_close(res.cost_of_waiting, 204949.65);
+    }
+
+    #[test]
+    fn loan_terms() {
+        // 200000 at 6% / 12 over 180 months, the first of the compared terms
+        let res = calculate_loan_terms(&request(json!({"amount": 200000, "rate": 6, "terms": [15, 30], "currency": "USD"}))).unwrap();
+        assert_close(res.terms[0].monthly_payment, 1687.71);
+        assert_close(res.terms[0].total_interest, 103788.82);
+        assert_close(res.lowest_monthly_payment, 1199.10);
+        assert_close(res.lowest_total_interest, 103788.82);
     }
 }
@@ -0,0 +1,1392 @@
+use crate::calculators::{
+    compound_growth, debt_payoff_timeline, fire_projection, inflation_path, investment_growth, recurring_cost_growth, retirement_trials,
+    raise_trajectories, round_up_growth,
+};
+use crate::i18n::{chart_text, format_chart_value, Chart, Lang};
+use crate::models::*;
+use std::cell::RefCell;
+
+/// Colours of everything in a chart except the data series.
+pub struct ChartTheme {
+    pub background: &'static str,
+    pub text: &'static str,
+    pub grid: &'static str,
+}
+
+const LIGHT: ChartTheme = ChartTheme { background: "white", text: "black", grid: "#7f8c8d" };
+
+/// Matches Telegram's dark theme; the series colours stay readable on it.
+const DARK: ChartTheme = ChartTheme { background: "#17212b", text: "#ecf0f1", grid: "#5d6d7e" };
+
+/// `theme` is "light" (default) or "dark".
+pub fn chart_theme(theme: Option<&str>) -> &'static ChartTheme {
+    match theme {
+        Some("dark") => &DARK,
+        _ => &LIGHT,
+    }
+}
+
+/// Series colours by the role they play, so a palette can swap them all at once.
+pub struct Palette {
+    /// Gains, savings and the better outcome.
+    pub positive: &'static str,
+    /// Darker companion of `positive` when both appear in one chart.
+    pub positive_dark: &'static str,
+    /// Costs, debt, interest and taxes.
+    pub negative: &'static str,
+    /// Neutral amounts such as contributions or principal.
+    pub primary: &'static str,
+    /// Contrasts with `positive` without meaning a loss.
+    pub accent: &'static str,
+    pub highlight: &'static str,
+    pub alternative: &'static str,
+    pub warning: &'static str,
+    pub muted: &'static str,
+    /// Unfilled part of a progress bar.
+    pub track: &'static str,
+}
+
+const DEFAULT_PALETTE: Palette = Palette {
+    positive: "#2ecc71",
+    positive_dark: "#27ae60",
+    negative: "#e74c3c",
+    primary: "#3498db",
+    accent: "#e67e22",
+    highlight: "#f1c40f",
+    alternative: "#9b59b6",
+    warning: "#f39c12",
+    muted: "#95a5a6",
+    track: "#bdc3c7",
+};
+
+/// Okabe-Ito colours: blue for good and orange for bad instead of the default
+/// green/red pair, with pairs that also differ in lightness.
+const COLORBLIND_PALETTE: Palette = Palette {
+    positive: "#0072b2",
+    positive_dark: "#004a75",
+    negative: "#e69f00",
+    primary: "#56b4e9",
+    accent: "#d55e00",
+    highlight: "#f0e442",
+    alternative: "#cc79a7",
+    warning: "#d55e00",
+    muted: "#999999",
+    track: "#dddddd",
+};
+
+/// `palette` is "default" or "colorblind".
+pub fn chart_palette(palette: Option<&str>) -> &'static Palette {
+    match palette {
+        Some("colorblind") => &COLORBLIND_PALETTE,
+        _ => &DEFAULT_PALETTE,
+    }
+}
+
+/// Opening `<svg>` tag and background; text inherits the theme's colour.
+fn svg_header(width: i32, height: i32, theme: &ChartTheme) -> String {
+    let mut svg = format!(
+        r#"<svg width="{}" height="{}" viewBox="0 0 {} {}" xmlns="http://www.w3.org/2000/svg" fill="{}">"#,
+        width, height, width, height, theme.text
+    );
+
+    // Background
+    svg.push_str(&format!(r#"<rect width="100%" height="100%" fill="{}" />"#, theme.background));
+    svg
+}
+
+/// Stands in for the chart's id inside element ids until `finish_svg` knows it.
+const ID_PLACEHOLDER: &str = "{chart-id}";
+
+/// Closes the `<svg>` and gives it an id hashed from its content, so charts
+/// sharing a page never collide. Inner ids written with `ID_PLACEHOLDER`
+/// become `<chart id>-...`.
+fn finish_svg(mut svg: String) -> String {
+    svg.push_str("</svg>");
+    // FNV-1a rather than std's hasher, whose output may change between releases
+    let hash = svg.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+    let id = format!("chart-{:016x}", hash);
+    svg.replacen("<svg ", &format!(r#"<svg id="{}" "#, id), 1).replace(ID_PLACEHOLDER, &id)
+}
+
+/// Bounds a requested chart size is clamped to, in pixels.
+const MIN_CHART_WIDTH: u32 = 200;
+const MAX_CHART_WIDTH: u32 = 1600;
+const MIN_CHART_HEIGHT: u32 = 100;
+const MAX_CHART_HEIGHT: u32 = 1200;
+
+/// Value of a numeric attribute on the root `<svg>` tag.
+fn root_attribute(root: &str, name: &str) -> Option<u32> {
+    let start = root.find(&format!(r#" {}=""#, name))? + name.len() + 3;
+    root[start..].split('"').next()?.parse().ok()
+}
+
+/// Sets the root `width` and `height` of a rendered chart, leaving its
+/// `viewBox` so the drawing scales into the new size; a different aspect ratio
+/// centres it with margins rather than distorting it. A side that isn't given
+/// keeps the chart's own proportions, and both are clamped to sane bounds.
+pub fn resize(svg: &str, width: Option<u32>, height: Option<u32>) -> String {
+    let (root, rest) = svg.split_at(svg.find('>').unwrap_or(0));
+    let (Some(old_width), Some(old_height)) = (root_attribute(root, "width"), root_attribute(root, "height")) else {
+        return svg.to_string();
+    };
+    let (width, height) = match (width, height) {
+        (None, None) => return svg.to_string(),
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, (w as u64 * old_height as u64 / old_width.max(1) as u64) as u32),
+        (None, Some(h)) => ((h as u64 * old_width as u64 / old_height.max(1) as u64) as u32, h),
+    };
+    let width = width.clamp(MIN_CHART_WIDTH, MAX_CHART_WIDTH);
+    let height = height.clamp(MIN_CHART_HEIGHT, MAX_CHART_HEIGHT);
+
+    let root = root
+        .replacen(&format!(r#" width="{}""#, old_width), &format!(r#" width="{}""#, width), 1)
+        .replacen(&format!(r#" height="{}""#, old_height), &format!(r#" height="{}""#, height), 1);
+    format!("{}{}", root, rest)
+}
+
+/// Escapes text for use inside SVG elements and attributes.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+thread_local! {
+    /// Bars and slices drawn while `recording_series` runs; `None` otherwise.
+    static SERIES: RefCell<Option<Vec<SeriesPoint>>> = const { RefCell::new(None) };
+}
+
+fn record_series(labels: &[&str], values: &[f64]) {
+    SERIES.with(|series| {
+        if let Some(series) = series.borrow_mut().as_mut() {
+            series.extend(labels.iter().zip(values).map(|(&label, &value)| SeriesPoint { label: label.to_string(), value }));
+        }
+    });
+}
+
+/// Runs `render` and returns the label/value pairs of the bar and donut charts
+/// it drew, exactly as they were plotted. Line charts are not included.
+pub fn recording_series(render: impl FnOnce()) -> Vec<SeriesPoint> {
+    SERIES.with(|series| *series.borrow_mut() = Some(Vec::new()));
+    render();
+    SERIES.with(|series| series.borrow_mut().take()).unwrap_or_default()
+}
+
+/// Number of horizontal grid lines drawn behind the bars.
+const GRID_LINES: i32 = 4;
+
+/// Smallest 1, 2 or 5 times a power of ten that splits `max_val` into `GRID_LINES` steps.
+fn nice_step(max_val: f64) -> f64 {
+    if max_val <= 0.0 || !max_val.is_finite() {
+        return 1.0;
+    }
+    let raw = max_val / GRID_LINES as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let nice = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .find(|&n| n * magnitude >= raw)
+        .unwrap_or(10.0);
+    nice * magnitude
+}
+
+/// Narrowest bar the bar charts draw; with many bars the chart grows wider instead.
+const MIN_BAR_WIDTH: i32 = 8;
+
+/// Bar slots narrower than this get their labels rotated so they don't overlap.
+const ROTATE_LABELS_BELOW: i32 = 40;
+
+fn create_bar_chart(title: &str, labels: Vec<&str>, values: Vec<f64>, colors: Vec<&str>, theme: &ChartTheme, lang: Lang) -> String {
+    let height = 300;
+    let padding = 40;
+    // Wider on the left to fit the axis labels
+    let padding_left = 60;
+    let slots = labels.len().max(1) as i32;
+    let chart_width = (400 - padding_left - padding)
+        .max(slots * (MIN_BAR_WIDTH + 10))
+        .min(MAX_CHART_WIDTH as i32 - padding_left - padding);
+    let width = padding_left + chart_width + padding;
+    let chart_height = height - padding * 2;
+    record_series(&labels, &values);
+    
+    // The axis always includes zero; negative values add grid levels below it
+    let finite = values.iter().cloned().filter(|v| v.is_finite());
+    let max_val = finite.clone().fold(0.0, f64::max);
+    let min_val = finite.fold(0.0, f64::min);
+    let step = nice_step(max_val - min_val);
+    let below = (-min_val / step).ceil() as i32;
+    let levels = (below + (max_val / step).ceil() as i32).max(GRID_LINES);
+    let scale = chart_height as f64 / (step * levels as f64);
+    let baseline = height - padding - (below as f64 * step * scale).round() as i32;
+    // Enough decimals to tell fractional steps apart
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    
+    let bar_width = (chart_width / slots - 10).max(1);
+    let rotate_labels = bar_width + 10 < ROTATE_LABELS_BELOW;
+    
+    let mut svg = svg_header(width, height, theme);
+    
+    // Title
+    svg.push_str(&format!(
+        r#"<text x="{}" y="25" font-family="sans-serif" font-size="16" font-weight="bold" text-anchor="middle">{}</text>"#,
+        width / 2, title
+    ));
+    
+    // Grid lines with their values on the left margin
+    for level in -below..=levels - below {
+        let y = baseline - (level as f64 * step * scale).round() as i32;
+        if level > -below {
+            svg.push_str(&format!(
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="0.5" />"#,
+                padding_left, y, width - padding, y, theme.grid
+            ));
+        }
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="end">{:.*}</text>"#,
+            padding_left - 5, y + 3, decimals, level as f64 * step
+        ));
+    }
+    
+    for (i, (&label, &value)) in labels.iter().zip(values.iter()).enumerate() {
+        let x = padding_left + i as i32 * (bar_width + 10) + 5;
+        let h = (value.abs() * scale) as i32;
+        // Positive bars stand on the zero line, negative ones hang below it
+        let y = if value < 0.0 { baseline } else { baseline - h };
+        let color = colors.get(i).unwrap_or(&"#3498db");
+        
+        svg.push_str(&format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="4" />"#,
+            x, y, bar_width, h, color
+        ));
+        
+        if rotate_labels {
+            let (lx, ly) = (x + bar_width / 2, height - padding + 10);
+            svg.push_str(&format!(
+                r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="end" transform="rotate(-45 {} {})">{}</text>"#,
+                lx, ly, lx, ly, label
+            ));
+        } else {
+            svg.push_str(&format!(
+                r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="middle">{}</text>"#,
+                x + bar_width / 2, height - padding + 15, label
+            ));
+        }
+        
+        // Above the bar, or above the zero line for a negative one
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" font-weight="bold" text-anchor="middle">{}</text>"#,
+            x + bar_width / 2, y - 5, format_chart_value(value, lang)
+        ));
+    }
+    
+    finish_svg(svg)
+}
+
+fn create_pie_chart(title: &str, labels: Vec<&str>, values: Vec<f64>, colors: Vec<&str>, theme: &ChartTheme, lang: Lang) -> String {
+    let width = 400;
+    let height = 300;
+    let cx = 200.0;
+    let cy = 150.0;
+    let outer = 95.0;
+    let inner = 55.0;
+    record_series(&labels, &values);
+
+    // Negative parts cannot be drawn as arcs
+    let values: Vec<f64> = values.iter().map(|v| v.max(0.0)).collect();
+    let total: f64 = values.iter().sum();
+
+    let mut svg = svg_header(width, height, theme);
+
+    // Title
+    svg.push_str(&format!(
+        r#"<text x="{}" y="25" font-family="sans-serif" font-size="16" font-weight="bold" text-anchor="middle">{}</text>"#,
+        width / 2, title
+    ));
+
+    if total > 0.0 {
+        let mut angle = -std::f64::consts::FRAC_PI_2;
+        for (i, &value) in values.iter().enumerate() {
+            let fraction = value / total;
+            let color = colors.get(i).unwrap_or(&"#3498db");
+            if fraction >= 1.0 {
+                svg.push_str(&format!(r#"<circle cx="{}" cy="{}" r="{}" fill="{}" />"#, cx, cy, outer, color));
+                break;
+            }
+            if fraction <= 0.0 {
+                continue;
+            }
+            let end = angle + fraction * std::f64::consts::TAU;
+            let large_arc = if fraction > 0.5 { 1 } else { 0 };
+            svg.push_str(&format!(
+                r#"<path d="M{:.2} {:.2} A{} {} 0 {} 1 {:.2} {:.2} L{} {} Z" fill="{}" />"#,
+                cx + outer * angle.cos(), cy + outer * angle.sin(),
+                outer, outer, large_arc,
+                cx + outer * end.cos(), cy + outer * end.sin(),
+                cx, cy, color
+            ));
+            angle = end;
+        }
+    } else {
+        svg.push_str(&format!(r##"<circle cx="{}" cy="{}" r="{}" fill="#ecf0f1" />"##, cx, cy, outer));
+    }
+
+    // Donut hole with the total in the middle
+    svg.push_str(&format!(r#"<circle cx="{}" cy="{}" r="{}" fill="{}" />"#, cx, cy, inner, theme.background));
+    svg.push_str(&format!(
+        r#"<text x="{}" y="{}" font-family="sans-serif" font-size="14" font-weight="bold" text-anchor="middle">{}</text>"#,
+        cx, cy + 5.0, format_chart_value(total, lang)
+    ));
+
+    // Legend
+    let slot = width as f64 / labels.len().max(1) as f64;
+    for (i, &label) in labels.iter().enumerate() {
+        let x = slot * i as f64 + slot / 2.0;
+        let color = colors.get(i).unwrap_or(&"#3498db");
+        svg.push_str(&format!(
+            r#"<rect x="{:.1}" y="{}" width="10" height="10" fill="{}" />"#,
+            x - 40.0, height - 22, color
+        ));
+        svg.push_str(&format!(
+            r#"<text x="{:.1}" y="{}" font-family="sans-serif" font-size="10">{}</text>"#,
+            x - 26.0, height - 13, label
+        ));
+    }
+
+    finish_svg(svg)
+}
+
+/// Renders a part-to-whole breakdown as bars, or as a donut when `chart_type` is "pie".
+fn create_breakdown_chart(
+    chart_type: Option<&str>,
+    title: &str,
+    labels: Vec<&str>,
+    values: Vec<f64>,
+    colors: Vec<&str>,
+    theme: &ChartTheme,
+    lang: Lang,
+) -> String {
+    match chart_type {
+        Some("pie") => create_pie_chart(title, labels, values, colors, theme, lang),
+        _ => create_bar_chart(title, labels, values, colors, theme, lang),
+    }
+}
+
+/// Bars built from stacked segments, with a legend naming the segments.
+/// `segments[bar][k]` is drawn in `colors[k]`; segment heights are rounded
+/// from running totals so they always add up to the bar's height.
+fn create_stacked_bar_chart(
+    title: &str,
+    labels: Vec<&str>,
+    segments: Vec<Vec<f64>>,
+    segment_labels: Vec<&str>,
+    colors: Vec<&str>,
+    theme: &ChartTheme,
+    lang: Lang,
+) -> String {
+    let width = 400;
+    let height = 300;
+    let padding = 40;
+    // Room for the bar labels and the legend below the bars
+    let padding_bottom = 55;
+    let chart_width = width - padding * 2;
+    let chart_height = height - padding - padding_bottom;
+
+    let totals: Vec<f64> = segments.iter().map(|bar| bar.iter().map(|v| v.max(0.0)).sum()).collect();
+    let max_val = totals.iter().cloned().fold(0.0, f64::max);
+    let scale = if max_val > 0.0 { chart_height as f64 / max_val } else { 1.0 };
+
+    let bar_width = chart_width / labels.len().max(1) as i32 - 10;
+    let baseline = height - padding_bottom;
+
+    let mut svg = svg_header(width, height, theme);
+
+    // Title
+    svg.push_str(&format!(
+        r#"<text x="{}" y="25" font-family="sans-serif" font-size="16" font-weight="bold" text-anchor="middle">{}</text>"#,
+        width / 2, title
+    ));
+
+    for (i, (&label, bar)) in labels.iter().zip(&segments).enumerate() {
+        let x = padding + i as i32 * (bar_width + 10) + 5;
+        let total_height = (totals[i] * scale).round() as i32;
+
+        svg.push_str(&format!(r#"<g id="{}-bar-{}" data-bar="{}" data-height="{}">"#, ID_PLACEHOLDER, i, i, total_height));
+        let mut running = 0.0;
+        let mut top = baseline;
+        for (k, &value) in bar.iter().enumerate() {
+            running += value.max(0.0);
+            let y = baseline - (running * scale).round() as i32;
+            let h = top - y;
+            if h > 0 {
+                svg.push_str(&format!(
+                    r#"<rect data-bar="{}" height="{}" x="{}" y="{}" width="{}" fill="{}" />"#,
+                    i, h, x, y, bar_width, colors.get(k).unwrap_or(&"#3498db")
+                ));
+            }
+            top = y;
+        }
+        svg.push_str("</g>");
+
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="middle">{}</text>"#,
+            x + bar_width / 2, baseline + 15, label
+        ));
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" font-weight="bold" text-anchor="middle">{}</text>"#,
+            x + bar_width / 2, baseline - total_height - 5, format_chart_value(totals[i], lang)
+        ));
+    }
+
+    // Legend
+    let slot = width as f64 / segment_labels.len().max(1) as f64;
+    for (k, &label) in segment_labels.iter().enumerate() {
+        let x = slot * k as f64 + 10.0;
+        svg.push_str(&format!(
+            r#"<rect x="{:.1}" y="{}" width="10" height="10" fill="{}" />"#,
+            x, height - 22, colors.get(k).unwrap_or(&"#3498db")
+        ));
+        svg.push_str(&format!(
+            r#"<text x="{:.1}" y="{}" font-family="sans-serif" font-size="10">{}</text>"#,
+            x + 14.0, height - 13, label
+        ));
+    }
+
+    finish_svg(svg)
+}
+
+/// Bars of every series side by side within each category, with a legend
+/// naming the series. Each series is a name and one value per category.
+fn create_grouped_bar_chart(
+    title: &str,
+    categories: Vec<&str>,
+    series: Vec<(&str, Vec<f64>)>,
+    colors: Vec<&str>,
+    theme: &ChartTheme,
+) -> String {
+    let height = 300;
+    let padding = 40;
+    // Wider on the left to fit the axis labels
+    let padding_left = 60;
+    // Room for the category labels and the legend below the bars
+    let padding_bottom = 55;
+    let groups = categories.len().max(1) as i32;
+    let bars = series.len().max(1) as i32;
+    let chart_width = (400 - padding_left - padding)
+        .max(groups * (bars * MIN_BAR_WIDTH + 10))
+        .min(MAX_CHART_WIDTH as i32 - padding_left - padding);
+    let width = padding_left + chart_width + padding;
+    let chart_height = height - padding - padding_bottom;
+
+    let max_val = series.iter().flat_map(|(_, values)| values.iter().cloned()).fold(0.0, f64::max);
+    let step = nice_step(max_val);
+    let scale = chart_height as f64 / (step * GRID_LINES as f64);
+    // Enough decimals to tell fractional steps apart
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+
+    let group_width = chart_width / groups;
+    let bar_width = ((group_width - 10) / bars).max(1);
+    let baseline = height - padding_bottom;
+
+    let mut svg = svg_header(width, height, theme);
+
+    // Title
+    svg.push_str(&format!(
+        r#"<text x="{}" y="25" font-family="sans-serif" font-size="16" font-weight="bold" text-anchor="middle">{}</text>"#,
+        width / 2, title
+    ));
+
+    // Grid lines with their values on the left margin
+    for level in 0..=GRID_LINES {
+        let y = baseline - (level as f64 * step * scale).round() as i32;
+        if level > 0 {
+            svg.push_str(&format!(
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="0.5" />"#,
+                padding_left, y, width - padding, y, theme.grid
+            ));
+        }
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="end">{:.*}</text>"#,
+            padding_left - 5, y + 3, decimals, level as f64 * step
+        ));
+    }
+
+    for (i, &category) in categories.iter().enumerate() {
+        let group_x = padding_left + i as i32 * group_width + 5;
+        for (k, (_, values)) in series.iter().enumerate() {
+            let value = values.get(i).copied().unwrap_or(0.0).max(0.0);
+            let x = group_x + k as i32 * bar_width;
+            let h = (value * scale) as i32;
+            svg.push_str(&format!(
+                r#"<rect data-series="{}" x="{}" y="{}" width="{}" height="{}" fill="{}" />"#,
+                k, x, baseline - h, bar_width, h, colors.get(k).unwrap_or(&"#3498db")
+            ));
+        }
+
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="middle">{}</text>"#,
+            group_x + (group_width - 10) / 2, baseline + 15, category
+        ));
+    }
+
+    // Legend
+    let slot = width as f64 / series.len().max(1) as f64;
+    for (k, (name, _)) in series.iter().enumerate() {
+        let x = slot * k as f64 + 10.0;
+        svg.push_str(&format!(
+            r#"<rect x="{:.1}" y="{}" width="10" height="10" fill="{}" />"#,
+            x, height - 22, colors.get(k).unwrap_or(&"#3498db")
+        ));
+        svg.push_str(&format!(
+            r#"<text x="{:.1}" y="{}" font-family="sans-serif" font-size="10">{}</text>"#,
+            x + 14.0, height - 13, name
+        ));
+    }
+
+    finish_svg(svg)
+}
+
+/// Maximum number of points plotted by `create_line_chart`, keeps long series small.
+const MAX_LINE_POINTS: usize = 120;
+
+/// Plots `points` as a polyline; `target` adds a dashed horizontal reference line.
+fn create_line_chart(title: &str, x_label: &str, points: Vec<(f64, f64)>, target: Option<f64>, color: &str, theme: &ChartTheme) -> String {
+    create_multi_line_chart(title, x_label, vec![("", points)], target, vec![color], theme)
+}
+
+/// Plots each named series as a polyline on shared axes, with a legend when
+/// the series have names; `target` adds a dashed horizontal reference line.
+fn create_multi_line_chart(
+    title: &str,
+    x_label: &str,
+    series: Vec<(&str, Vec<(f64, f64)>)>,
+    target: Option<f64>,
+    colors: Vec<&str>,
+    theme: &ChartTheme,
+) -> String {
+    let width = 400;
+    let height = 300;
+    let padding = 40;
+    let chart_width = (width - padding * 2) as f64;
+    let chart_height = (height - padding * 2) as f64;
+
+    let sampled: Vec<Vec<(f64, f64)>> = series
+        .iter()
+        .map(|(_, points)| {
+            let step = points.len().div_ceil(MAX_LINE_POINTS).max(1);
+            let mut sampled: Vec<(f64, f64)> = points.iter().step_by(step).cloned().collect();
+            if let Some(&last) = points.last()
+                && sampled.last() != Some(&last)
+            {
+                sampled.push(last);
+            }
+            sampled
+        })
+        .collect();
+
+    let max_x = sampled.iter().flatten().map(|p| p.0).fold(0.0, f64::max);
+    let max_y = sampled.iter().flatten().map(|p| p.1).fold(target.unwrap_or(0.0), f64::max);
+    let scale_x = if max_x > 0.0 { chart_width / max_x } else { 1.0 };
+    let scale_y = if max_y > 0.0 { chart_height / max_y } else { 1.0 };
+
+    let mut svg = svg_header(width, height, theme);
+
+    // Title
+    svg.push_str(&format!(
+        r#"<text x="{}" y="25" font-family="sans-serif" font-size="16" font-weight="bold" text-anchor="middle">{}</text>"#,
+        width / 2, title
+    ));
+
+    // Axes
+    svg.push_str(&format!(
+        r#"<path d="M{} {} V{} H{}" fill="none" stroke="{}" stroke-width="1" />"#,
+        padding, padding, height - padding, width - padding, theme.grid
+    ));
+
+    if let Some(target) = target {
+        let y = (height - padding) as f64 - target * scale_y;
+        svg.push_str(&format!(
+            r##"<line x1="{}" y1="{:.1}" x2="{}" y2="{:.1}" stroke="#e74c3c" stroke-width="1" stroke-dasharray="4 4" />"##,
+            padding, y, width - padding, y
+        ));
+    }
+
+    for (k, points) in sampled.iter().enumerate() {
+        let coords: Vec<String> = points
+            .iter()
+            .map(|&(x, y)| format!("{:.1},{:.1}", padding as f64 + x * scale_x, (height - padding) as f64 - y * scale_y))
+            .collect();
+        svg.push_str(&format!(
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2" />"#,
+            coords.join(" "), colors.get(k).unwrap_or(&"#3498db")
+        ));
+    }
+
+    // Axis labels
+    svg.push_str(&format!(
+        r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="middle">{}</text>"#,
+        width / 2, height - padding + 25, x_label
+    ));
+    svg.push_str(&format!(
+        r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" text-anchor="end">{}</text>"#,
+        width - padding, height - padding + 15, max_x.round()
+    ));
+    svg.push_str(&format!(
+        r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10" font-weight="bold" text-anchor="start">{}</text>"#,
+        padding + 5, padding - 5, max_y.round()
+    ));
+
+    // Legend below the x axis on the left, only for named series
+    for (k, (name, _)) in series.iter().enumerate().filter(|(_, (name, _))| !name.is_empty()) {
+        let y = height - padding + 8 + k as i32 * 14;
+        svg.push_str(&format!(
+            r#"<rect x="{}" y="{}" width="10" height="10" fill="{}" />"#,
+            padding, y, colors.get(k).unwrap_or(&"#3498db")
+        ));
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10">{}</text>"#,
+            padding + 14, y + 9, name
+        ));
+    }
+
+    finish_svg(svg)
+}
+
+/// Horizontal bar split into the done and remaining share of a goal, `percent` in 0..=100.
+fn create_progress_bar(title: &str, labels: Vec<&str>, percent: f64, colors: Vec<&str>, theme: &ChartTheme) -> String {
+    let width = 400;
+    let height = 120;
+    let padding = 20;
+    let bar_width = width - padding * 2;
+    let bar_height = 30;
+    let bar_y = 45;
+
+    let done_width = (bar_width as f64 * percent.clamp(0.0, 100.0) / 100.0).round() as i32;
+
+    let mut svg = svg_header(width, height, theme);
+
+    // Title
+    svg.push_str(&format!(
+        r#"<text x="{}" y="25" font-family="sans-serif" font-size="16" font-weight="bold" text-anchor="middle">{}</text>"#,
+        width / 2, title
+    ));
+
+    let segments = [(padding, done_width), (padding + done_width, bar_width - done_width)];
+    for (k, &(x, w)) in segments.iter().enumerate() {
+        if w > 0 {
+            svg.push_str(&format!(
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" />"#,
+                x, bar_y, w, bar_height, colors.get(k).unwrap_or(&"#3498db")
+            ));
+        }
+    }
+    svg.push_str(&format!(
+        r#"<text x="{}" y="{}" font-family="sans-serif" font-size="12" font-weight="bold" text-anchor="middle">{:.1}%</text>"#,
+        width / 2, bar_y + bar_height / 2 + 4, percent
+    ));
+
+    // Legend
+    for (k, &label) in labels.iter().enumerate() {
+        let x = padding + k as i32 * bar_width / 2;
+        svg.push_str(&format!(
+            r#"<rect x="{}" y="{}" width="10" height="10" fill="{}" />"#,
+            x, height - 22, colors.get(k).unwrap_or(&"#3498db")
+        ));
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}" font-family="sans-serif" font-size="10">{}</text>"#,
+            x + 14, height - 13, label
+        ));
+    }
+
+    finish_svg(svg)
+}
+
+// Renderers for each calculator, run by the router after the math when the
+// request leaves `include_chart` on. They only read the request and the result.
+
+pub fn hourly_income(req: &HourlyIncomeRequest, res: &mut HourlyIncomeResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::HourlyIncome, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.nominal_hourly_income, res.real_hourly_income],
+        vec![palette.muted, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn time_value(req: &TimeValueRequest, res: &mut TimeValueResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let hourly = res.time_value;
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::TimeValue, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![hourly, hourly * 8.0, hourly * 40.0, hourly * 160.0],
+        vec![palette.primary, palette.primary, palette.primary, palette.primary],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn investment(req: &InvestmentRequest, res: &mut InvestmentResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+
+    // simplified "chart" for investment (just end state comparison)
+    let text = chart_text(Chart::Investment, lang);
+    res.chart = create_breakdown_chart(
+        req.chart_type.as_deref(),
+        text.title,
+        text.labels.to_vec(),
+        vec![res.total_contributions, res.total_gain],
+        vec![palette.primary, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+
+    if req.include_growth_chart.unwrap_or(false)
+        && let Ok(points) = investment_growth(req)
+    {
+        let text = chart_text(Chart::InvestmentGrowth, lang);
+        res.growth_chart = Some(create_line_chart(text.title, text.labels[0], points, None, palette.positive, chart_theme(req.options.theme.as_deref())));
+    }
+
+    if let Some(scenarios) = &res.scenarios {
+        // Contributions are the same in every scenario, so the gap to the final value is the gain
+        let text = chart_text(Chart::InvestmentScenarios, lang);
+        let series = chart_text(Chart::InvestmentScenarioSeries, lang);
+        res.scenario_chart = Some(create_grouped_bar_chart(
+            text.title,
+            text.labels.to_vec(),
+            vec![
+                (series.labels[0], vec![res.total_contributions; 3]),
+                (series.labels[1], vec![scenarios.low, scenarios.expected, scenarios.high]),
+            ],
+            vec![palette.primary, palette.positive],
+            chart_theme(req.options.theme.as_deref()),
+        ));
+    }
+}
+
+pub fn credit(req: &CreditRequest, res: &mut CreditResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Credit, lang);
+    res.chart = create_breakdown_chart(
+        req.chart_type.as_deref(),
+        text.title,
+        text.labels.to_vec(),
+        vec![req.amount, res.overpayment],
+        vec![palette.primary, palette.negative],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn retirement(req: &RetirementRequest, res: &mut RetirementResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Retirement, lang);
+    let segments = chart_text(Chart::RetirementSegments, lang);
+
+    // The projection stacks what today's savings and the monthly savings grow into
+    res.chart = create_stacked_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![
+            vec![res.future_value_savings, res.future_value_contributions, 0.0],
+            vec![0.0, 0.0, res.required_capital],
+        ],
+        vec![segments.labels[0], segments.labels[1], text.labels[1]],
+        vec![palette.positive_dark, palette.positive, palette.accent],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn debt_payoff(req: &DebtPayoffRequest, res: &mut DebtPayoffResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    // An unpayable debt shows the monthly interest the payment fails to cover
+    let interest = if res.payable {
+        res.total_interest
+    } else {
+        req.balance * req.interest_rate / 100.0 / 12.0
+    };
+
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::DebtPayoff, lang);
+    res.chart = create_breakdown_chart(
+        req.chart_type.as_deref(),
+        text.title,
+        text.labels.to_vec(),
+        vec![req.balance, interest],
+        vec![palette.primary, palette.negative],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+
+    let timeline = debt_payoff_timeline(req);
+    if !timeline.is_empty() {
+        let text = chart_text(Chart::DebtPayoffTimeline, lang);
+        res.timeline_chart = Some(create_multi_line_chart(
+            text.title,
+            text.labels[0],
+            vec![
+                (text.labels[1], timeline.iter().map(|&(month, balance, _)| (month, balance)).collect()),
+                (text.labels[2], timeline.iter().map(|&(month, _, interest)| (month, interest)).collect()),
+            ],
+            None,
+            vec![palette.primary, palette.negative],
+            chart_theme(req.options.theme.as_deref()),
+        ));
+    }
+}
+
+pub fn debt_strategy(req: &DebtStrategyRequest, res: &mut DebtStrategyResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::DebtStrategy, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.snowball.total_interest, res.avalanche.total_interest],
+        vec![palette.primary, palette.accent],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn emergency_fund(req: &EmergencyFundRequest, res: &mut EmergencyFundResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::EmergencyFund, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![req.current_savings, res.target_amount],
+        vec![palette.primary, palette.highlight],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn tax(req: &TaxRequest, res: &mut TaxResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Tax, lang);
+    res.chart = create_breakdown_chart(
+        req.chart_type.as_deref(),
+        text.title,
+        text.labels.to_vec(),
+        vec![res.net_income, res.tax_amount],
+        vec![palette.positive, palette.negative],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn buy_rent(req: &BuyRentRequest, res: &mut BuyRentResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::BuyRent, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.net_buy_position, res.net_rent_position],
+        vec![palette.positive, palette.primary],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn savings_rate(req: &SavingsRateRequest, res: &mut SavingsRateResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let monthly_savings = req.monthly_income - req.monthly_expenses;
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::SavingsRate, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![req.monthly_income, req.monthly_expenses, monthly_savings.max(0.0)],
+        vec![palette.primary, palette.negative, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn fire(req: &FireRequest, res: &mut FireResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let text = chart_text(Chart::Fire, Lang::from_code(req.options.lang.as_deref()));
+    res.chart = create_line_chart(
+        text.title,
+        text.labels[0],
+        fire_projection(req),
+        Some(res.fire_target),
+        palette.positive,
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn compound(req: &CompoundRequest, res: &mut CompoundResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    if let Ok(points) = compound_growth(req) {
+        let text = chart_text(Chart::Compound, Lang::from_code(req.options.lang.as_deref()));
+        res.chart = create_line_chart(text.title, text.labels[0], points, None, palette.positive, chart_theme(req.options.theme.as_deref()));
+    }
+}
+
+pub fn freelance_rate(req: &FreelanceRateRequest, res: &mut FreelanceRateResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    // What each billable hour pays for: take-home pay vs taxes and expenses
+    let take_home = req.desired_net_income / req.billable_hours;
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::FreelanceRate, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![take_home, res.hourly_rate - take_home],
+        vec![palette.positive, palette.accent],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn wage_convert(req: &WageConvertRequest, res: &mut WageConvertResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::TimeValue, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.hourly, res.daily, res.weekly, res.monthly],
+        vec![palette.primary, palette.primary, palette.primary, palette.primary],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn refinance(req: &RefinanceRequest, res: &mut RefinanceResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    // The new loan's cost includes its closing costs
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Refinance, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.current_total_interest, res.new_total_interest + req.closing_costs],
+        vec![palette.negative, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+/// Number of histogram buckets for Monte Carlo outcomes.
+const HISTOGRAM_BUCKETS: usize = 6;
+
+/// Short axis label for large amounts, e.g. 1.2M or 350k.
+fn compact_amount(value: f64) -> String {
+    if value.abs() >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if value.abs() >= 1_000.0 {
+        format!("{:.0}k", value / 1_000.0)
+    } else {
+        format!("{:.0}", value)
+    }
+}
+
+pub fn retirement_monte_carlo(req: &RetirementMonteCarloRequest, res: &mut RetirementMonteCarloResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let outcomes = retirement_trials(req);
+    let (min, max) = match (outcomes.first(), outcomes.last()) {
+        (Some(&min), Some(&max)) => (min, max),
+        _ => return,
+    };
+
+    // Equal-width buckets labelled by their upper bound; the ones that reach
+    // the required capital are green
+    let width = ((max - min) / HISTOGRAM_BUCKETS as f64).max(f64::EPSILON);
+    let mut counts = vec![0.0; HISTOGRAM_BUCKETS];
+    for value in &outcomes {
+        let bucket = (((value - min) / width) as usize).min(HISTOGRAM_BUCKETS - 1);
+        counts[bucket] += 1.0;
+    }
+    let labels: Vec<String> = (1..=HISTOGRAM_BUCKETS)
+        .map(|i| format!("≤{}", compact_amount(min + width * i as f64)))
+        .collect();
+    let colors: Vec<&str> = (0..HISTOGRAM_BUCKETS)
+        .map(|i| if min + width * i as f64 >= res.required_capital { palette.positive } else { palette.accent })
+        .collect();
+
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::RetirementMonteCarlo, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        labels.iter().map(String::as_str).collect(),
+        counts,
+        colors,
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn opportunity_cost(req: &OpportunityCostRequest, res: &mut OpportunityCostResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::OpportunityCost, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![req.amount, res.future_value],
+        vec![palette.negative, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn goal_progress(req: &GoalProgressRequest, res: &mut GoalProgressResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let text = chart_text(Chart::GoalProgress, Lang::from_code(req.options.lang.as_deref()));
+    res.chart = create_progress_bar(
+        text.title,
+        text.labels.to_vec(),
+        res.percent_complete,
+        vec![palette.positive, palette.track],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn subscription(req: &SubscriptionRequest, res: &mut SubscriptionResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Subscription, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![req.monthly_cost, res.pay_per_use_cost.unwrap_or(0.0)],
+        vec![palette.alternative, palette.warning],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn wage_erosion(req: &WageErosionRequest, res: &mut WageErosionResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let text = chart_text(Chart::WageErosion, Lang::from_code(req.options.lang.as_deref()));
+    let nominal = res.years.iter().map(|y| (y.year as f64, y.nominal_salary)).collect();
+    let real = res.years.iter().map(|y| (y.year as f64, y.real_salary)).collect();
+    res.chart = create_multi_line_chart(
+        text.title,
+        text.labels[0],
+        vec![(text.labels[1], nominal), (text.labels[2], real)],
+        None,
+        vec![palette.primary, palette.negative],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn net_pay(req: &NetPayRequest, res: &mut NetPayResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::NetPay, lang);
+    res.chart = create_stacked_bar_chart(
+        text.title,
+        vec![text.labels[0]],
+        vec![vec![res.net_pay, res.income_tax, res.social_contributions, res.fixed_deductions]],
+        text.labels[1..].to_vec(),
+        vec![palette.positive, palette.negative, palette.warning, palette.muted],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn required_savings(req: &RequiredSavingsRequest, res: &mut RequiredSavingsResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Investment, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.total_contributions, res.total_growth],
+        vec![palette.primary, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn debt_vs_invest(req: &DebtVsInvestRequest, res: &mut DebtVsInvestResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::DebtVsInvest, lang);
+    let series = chart_text(Chart::DebtVsInvestSeries, lang);
+    res.chart = create_grouped_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![
+            (series.labels[0], vec![res.pay_debt.investments, res.invest.investments]),
+            (series.labels[1], vec![res.pay_debt.remaining_debt, res.invest.remaining_debt]),
+        ],
+        vec![palette.positive, palette.negative],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn lump_vs_dca(req: &LumpVsDcaRequest, res: &mut LumpVsDcaResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::LumpVsDca, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.lump_sum_value, res.dca_value],
+        vec![palette.positive, palette.primary],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn inflation(req: &InflationRequest, res: &mut InflationResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    if let Ok(points) = inflation_path(req) {
+        let text = chart_text(Chart::Inflation, Lang::from_code(req.options.lang.as_deref()));
+        res.chart = create_line_chart(text.title, text.labels[0], points, None, palette.negative, chart_theme(req.options.theme.as_deref()));
+    }
+}
+
+pub fn job_compare(req: &JobCompareRequest, res: &mut JobCompareResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::JobCompare, lang);
+    let series = chart_text(Chart::HourlyIncome, lang);
+    res.chart = create_grouped_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![
+            (series.labels[0], vec![res.offer_a.nominal_hourly_income, res.offer_b.nominal_hourly_income]),
+            (series.labels[1], vec![res.offer_a.real_hourly_income, res.offer_b.real_hourly_income]),
+        ],
+        vec![palette.muted, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn recurring_cost(req: &RecurringCostRequest, res: &mut RecurringCostResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let points = match recurring_cost_growth(req) {
+        Ok(points) => points,
+        Err(_) => return,
+    };
+
+    // The invested line only means something with a return to grow at
+    let text = chart_text(Chart::RecurringCost, Lang::from_code(req.options.lang.as_deref()));
+    let mut series = vec![(text.labels[1], points.iter().map(|&(year, spent, _)| (year, spent)).collect())];
+    if req.annual_return.is_some() {
+        series.push((text.labels[2], points.iter().map(|&(year, _, invested)| (year, invested)).collect()));
+    }
+    res.chart = create_multi_line_chart(
+        text.title,
+        text.labels[0],
+        series,
+        None,
+        vec![palette.negative, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn blended_rate(req: &BlendedRateRequest, res: &mut BlendedRateResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::BlendedRate, lang);
+
+    // One bar per debt, then the blended rate they add up to
+    let mut labels: Vec<String> = (1..=req.debts.len()).map(|i| format!("{} {}", text.labels[0], i)).collect();
+    labels.push(text.labels[1].to_string());
+    let mut rates: Vec<f64> = req.debts.iter().map(|d| d.rate).collect();
+    rates.push(res.blended_rate);
+    let mut colors = vec![palette.negative; req.debts.len()];
+    colors.push(palette.primary);
+
+    res.chart = create_bar_chart(
+        text.title,
+        labels.iter().map(String::as_str).collect(),
+        rates,
+        colors,
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn savings_compare(req: &SavingsCompareRequest, res: &mut SavingsCompareResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let text = chart_text(Chart::SavingsCompare, Lang::from_code(req.options.lang.as_deref()));
+    // Account names come from the request and end up inside the SVG
+    let names: Vec<String> = res.accounts.iter().map(|a| escape_xml(&a.name)).collect();
+    res.chart = create_grouped_bar_chart(
+        text.title,
+        names.iter().map(String::as_str).collect(),
+        vec![
+            (text.labels[0], vec![res.total_deposited; res.accounts.len()]),
+            (text.labels[1], res.accounts.iter().map(|a| a.final_balance).collect()),
+        ],
+        vec![palette.muted, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn round_up(req: &RoundUpRequest, res: &mut RoundUpResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let points = round_up_growth(req);
+
+    // Without a return the invested line would only repeat the saved one
+    let text = chart_text(Chart::RoundUp, Lang::from_code(req.options.lang.as_deref()));
+    let mut series = vec![(text.labels[1], points.iter().map(|&(year, saved, _)| (year, saved)).collect())];
+    if req.annual_return.is_some() {
+        series.push((text.labels[2], points.iter().map(|&(year, _, invested)| (year, invested)).collect()));
+    }
+    res.chart = create_multi_line_chart(
+        text.title,
+        text.labels[0],
+        series,
+        None,
+        vec![palette.primary, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn affordability(req: &AffordabilityRequest, res: &mut AffordabilityResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Affordability, lang);
+
+    // Where a month's income goes at the largest affordable payment
+    let rest = (req.monthly_income - req.monthly_debt_payments - res.max_monthly_payment).max(0.0);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![req.monthly_debt_payments, res.max_monthly_payment, rest],
+        vec![palette.negative, palette.primary, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn bill_split(req: &BillSplitRequest, res: &mut BillSplitResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::BillSplit, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.grand_total - res.total_tip, res.total_tip, res.per_person],
+        vec![palette.primary, palette.accent, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn raise_impact(req: &RaiseImpactRequest, res: &mut RaiseImpactResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let text = chart_text(Chart::RaiseImpact, Lang::from_code(req.options.lang.as_deref()));
+    let trajectories = raise_trajectories(req).unwrap_or_default();
+    let without = trajectories.iter().map(|&(year, without, _)| (year, without)).collect();
+    let with = trajectories.iter().map(|&(year, _, with)| (year, with)).collect();
+    res.chart = create_multi_line_chart(
+        text.title,
+        text.labels[0],
+        vec![(text.labels[1], without), (text.labels[2], with)],
+        None,
+        vec![palette.muted, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+pub fn budget(req: &BudgetRequest, res: &mut BudgetResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::Budget, lang);
+    res.chart = create_pie_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.needs, res.wants, res.savings],
+        vec![palette.primary, palette.accent, palette.positive],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn cost_of_waiting(req: &CostOfWaitingRequest, res: &mut CostOfWaitingResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let lang = Lang::from_code(req.options.lang.as_deref());
+    let text = chart_text(Chart::CostOfWaiting, lang);
+    res.chart = create_bar_chart(
+        text.title,
+        text.labels.to_vec(),
+        vec![res.start_now_value, res.start_later_value],
+        vec![palette.positive, palette.negative],
+        chart_theme(req.options.theme.as_deref()),
+        lang,
+    );
+}
+
+pub fn loan_terms(req: &LoanTermsRequest, res: &mut LoanTermsResponse) {
+    let palette = chart_palette(req.options.palette.as_deref());
+    let text = chart_text(Chart::LoanTerms, Lang::from_code(req.options.lang.as_deref()));
+    let terms: Vec<String> = res.terms.iter().map(|t| t.term.to_string()).collect();
+    res.chart = create_grouped_bar_chart(
+        text.title,
+        terms.iter().map(String::as_str).collect(),
+        vec![
+            (text.labels[0], res.terms.iter().map(|t| t.monthly_payment).collect()),
+            (text.labels[1], res.terms.iter().map(|t| t.total_interest).collect()),
+        ],
+        vec![palette.primary, palette.negative],
+        chart_theme(req.options.theme.as_deref()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values of every `width`/`height` attribute in `svg`, percentages included.
+    fn sizes(svg: &str) -> Vec<f64> {
+        ["width=\"", "height=\""]
+            .iter()
+            .flat_map(|attr| svg.split(attr).skip(1))
+            .map(|rest| rest.split('"').next().unwrap().trim_end_matches('%').parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn bar_chart_with_many_labels() {
+        let labels: Vec<String> = (1..=20).map(|i| format!("Item {}", i)).collect();
+        let mut values: Vec<f64> = (1..=20).map(|i| i as f64 * 100.0).collect();
+        values[3] = -250.0;
+        let svg = create_bar_chart(
+            "Many",
+            labels.iter().map(String::as_str).collect(),
+            values,
+            vec![],
+            &LIGHT,
+            Lang::En,
+        );
+        assert_eq!(svg.matches("<rect ").count(), 21);
+        for size in sizes(&svg) {
+            assert!(size >= 0.0, "negative size {} in {}", size, svg);
+        }
+    }
+
+    #[test]
+    fn bar_chart_width_is_capped() {
+        let labels: Vec<String> = (1..=500).map(|i| i.to_string()).collect();
+        let svg = create_bar_chart("Huge", labels.iter().map(String::as_str).collect(), vec![1.0; 500], vec![], &LIGHT, Lang::En);
+        assert!(svg.contains(&format!(r#"viewBox="0 0 {} 300""#, MAX_CHART_WIDTH)), "{}", &svg[..200]);
+        for size in sizes(&svg) {
+            assert!(size >= 0.0, "negative size {}", size);
+        }
+    }
+}
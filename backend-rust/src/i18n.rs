@@ -0,0 +1,263 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum Lang {
+    Uk,
+    En,
+}
+
+impl Lang {
+    pub fn from_code(code: Option<&str>) -> Lang {
+        match code {
+            Some("en") => Lang::En,
+            _ => Lang::Uk,
+        }
+    }
+}
+
+/// Amount printed on a chart, rounded to whole units: grouped thousands below
+/// a million ("12,345" in English, "12 345" in Ukrainian), abbreviated above
+/// ("1.5M" or "1,5 млн").
+pub fn format_chart_value(value: f64, lang: Lang) -> String {
+    let (group, decimal, million) = match lang {
+        Lang::Uk => (' ', ',', " млн"),
+        Lang::En => (',', '.', "M"),
+    };
+    if value.abs() >= 1_000_000.0 {
+        let number = format!("{:.1}", value / 1_000_000.0).replace('.', &decimal.to_string());
+        return format!("{}{}", number, million);
+    }
+
+    let digits = format!("{:.0}", value.abs());
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(group);
+        }
+        grouped.push(c);
+    }
+    // No "-0" for values that round to zero
+    if value.round() < 0.0 { format!("-{}", grouped) } else { grouped }
+}
+
+#[derive(Clone, Copy)]
+pub enum Chart {
+    HourlyIncome,
+    TimeValue,
+    Investment,
+    InvestmentGrowth,
+    InvestmentScenarios,
+    InvestmentScenarioSeries,
+    Credit,
+    Retirement,
+    RetirementSegments,
+    DebtPayoff,
+    DebtPayoffTimeline,
+    DebtStrategy,
+    EmergencyFund,
+    Tax,
+    BuyRent,
+    SavingsRate,
+    Fire,
+    Compound,
+    FreelanceRate,
+    Refinance,
+    RetirementMonteCarlo,
+    OpportunityCost,
+    GoalProgress,
+    Subscription,
+    WageErosion,
+    NetPay,
+    DebtVsInvest,
+    DebtVsInvestSeries,
+    LumpVsDca,
+    Inflation,
+    JobCompare,
+    RecurringCost,
+    BlendedRate,
+    SavingsCompare,
+    RoundUp,
+    Affordability,
+    BillSplit,
+    RaiseImpact,
+    Budget,
+    CostOfWaiting,
+    LoanTerms,
+}
+
+pub struct ChartText {
+    pub title: &'static str,
+    pub labels: &'static [&'static str],
+}
+
+pub fn chart_text(chart: Chart, lang: Lang) -> ChartText {
+    let (uk, en) = match chart {
+        Chart::HourlyIncome => (
+            ChartText { title: "Порівняння ставок", labels: &["Номінальна", "Реальна"] },
+            ChartText { title: "Hourly rate comparison", labels: &["Nominal", "Real"] },
+        ),
+        Chart::TimeValue => (
+            ChartText { title: "Вартість часу", labels: &["Година", "День", "Тиждень", "Місяць"] },
+            ChartText { title: "Value of time", labels: &["Hour", "Day", "Week", "Month"] },
+        ),
+        Chart::Investment => (
+            ChartText { title: "Структура капіталу", labels: &["Внески", "Прибуток"] },
+            ChartText { title: "Capital structure", labels: &["Contributions", "Gain"] },
+        ),
+        Chart::InvestmentGrowth => (
+            ChartText { title: "Зростання капіталу", labels: &["Роки"] },
+            ChartText { title: "Portfolio growth", labels: &["Years"] },
+        ),
+        Chart::InvestmentScenarios => (
+            ChartText { title: "Сценарії дохідності", labels: &["Низька", "Очікувана", "Висока"] },
+            ChartText { title: "Return scenarios", labels: &["Low", "Expected", "High"] },
+        ),
+        Chart::InvestmentScenarioSeries => (
+            ChartText { title: "Сценарії дохідності", labels: &["Вкладено", "Підсумок"] },
+            ChartText { title: "Return scenarios", labels: &["Contributed", "Final value"] },
+        ),
+        Chart::Credit => (
+            ChartText { title: "Структура виплат", labels: &["Тіло", "Переплата"] },
+            ChartText { title: "Payment structure", labels: &["Principal", "Overpayment"] },
+        ),
+        Chart::Retirement => (
+            ChartText { title: "Пенсійне забезпечення", labels: &["Матимете", "Необхідно"] },
+            ChartText { title: "Retirement provision", labels: &["Projected", "Required"] },
+        ),
+        Chart::RetirementSegments => (
+            ChartText { title: "Пенсійне забезпечення", labels: &["Заощадження", "Внески"] },
+            ChartText { title: "Retirement provision", labels: &["Savings", "Contributions"] },
+        ),
+        Chart::DebtPayoff => (
+            ChartText { title: "Структура боргу", labels: &["Борг", "Відсотки"] },
+            ChartText { title: "Debt structure", labels: &["Debt", "Interest"] },
+        ),
+        Chart::DebtPayoffTimeline => (
+            ChartText { title: "Погашення боргу", labels: &["Місяці", "Залишок", "Сплачені відсотки"] },
+            ChartText { title: "Debt payoff", labels: &["Months", "Balance", "Interest paid"] },
+        ),
+        Chart::DebtStrategy => (
+            ChartText { title: "Переплата за стратегіями", labels: &["Сніжна куля", "Лавина"] },
+            ChartText { title: "Interest by strategy", labels: &["Snowball", "Avalanche"] },
+        ),
+        Chart::EmergencyFund => (
+            ChartText { title: "Статус подушки", labels: &["Наявне", "Ціль"] },
+            ChartText { title: "Emergency fund status", labels: &["Saved", "Target"] },
+        ),
+        Chart::Tax => (
+            ChartText { title: "Структура доходу", labels: &["Чистий", "Податок"] },
+            ChartText { title: "Income structure", labels: &["Net", "Tax"] },
+        ),
+        Chart::BuyRent => (
+            ChartText { title: "Капітал через горизонт", labels: &["Купівля", "Оренда"] },
+            ChartText { title: "Capital over horizon", labels: &["Buy", "Rent"] },
+        ),
+        Chart::SavingsRate => (
+            ChartText { title: "Структура бюджету", labels: &["Дохід", "Витрати", "Заощадження"] },
+            ChartText { title: "Budget structure", labels: &["Income", "Expenses", "Savings"] },
+        ),
+        Chart::Fire => (
+            ChartText { title: "Шлях до фінансової незалежності", labels: &["Роки"] },
+            ChartText { title: "Path to financial independence", labels: &["Years"] },
+        ),
+        Chart::Compound => (
+            ChartText { title: "Складні відсотки", labels: &["Роки"] },
+            ChartText { title: "Compound interest", labels: &["Years"] },
+        ),
+        Chart::FreelanceRate => (
+            ChartText { title: "Структура ставки", labels: &["На руки", "Податки й витрати"] },
+            ChartText { title: "Rate breakdown", labels: &["Take-home", "Taxes & expenses"] },
+        ),
+        Chart::Refinance => (
+            ChartText { title: "Вартість кредиту", labels: &["Поточний", "Новий"] },
+            ChartText { title: "Cost of the loan", labels: &["Current", "New"] },
+        ),
+        Chart::RetirementMonteCarlo => (
+            ChartText { title: "Розподіл результатів", labels: &[] },
+            ChartText { title: "Distribution of outcomes", labels: &[] },
+        ),
+        Chart::OpportunityCost => (
+            ChartText { title: "Ціна втраченої можливості", labels: &["Витрачено", "Якби інвестували"] },
+            ChartText { title: "Opportunity cost", labels: &["Spent", "If invested"] },
+        ),
+        Chart::GoalProgress => (
+            ChartText { title: "Прогрес до цілі", labels: &["Накопичено", "Залишилось"] },
+            ChartText { title: "Goal progress", labels: &["Saved", "Remaining"] },
+        ),
+        Chart::Subscription => (
+            ChartText { title: "Вартість на місяць", labels: &["Підписка", "Оплата за раз"] },
+            ChartText { title: "Monthly cost", labels: &["Subscription", "Pay per use"] },
+        ),
+        Chart::WageErosion => (
+            ChartText { title: "Зарплата та інфляція", labels: &["Роки", "Номінальна", "Реальна"] },
+            ChartText { title: "Salary vs inflation", labels: &["Years", "Nominal", "Real"] },
+        ),
+        Chart::NetPay => (
+            ChartText { title: "Розрахунковий лист", labels: &["Брутто", "На руки", "ПДФО", "Внески", "Утримання"] },
+            ChartText { title: "Payslip", labels: &["Gross", "Net pay", "Income tax", "Contributions", "Deductions"] },
+        ),
+        Chart::DebtVsInvest => (
+            ChartText { title: "Борг чи інвестиції", labels: &["Погасити борг", "Інвестувати"] },
+            ChartText { title: "Debt or invest", labels: &["Pay off debt", "Invest"] },
+        ),
+        Chart::DebtVsInvestSeries => (
+            ChartText { title: "Борг чи інвестиції", labels: &["Інвестиції", "Залишок боргу"] },
+            ChartText { title: "Debt or invest", labels: &["Investments", "Remaining debt"] },
+        ),
+        Chart::LumpVsDca => (
+            ChartText { title: "Одразу чи частинами", labels: &["Одразу", "Частинами"] },
+            ChartText { title: "Lump sum or DCA", labels: &["Lump sum", "DCA"] },
+        ),
+        Chart::Inflation => (
+            ChartText { title: "Вплив інфляції", labels: &["Роки"] },
+            ChartText { title: "Effect of inflation", labels: &["Years"] },
+        ),
+        Chart::JobCompare => (
+            ChartText { title: "Порівняння пропозицій", labels: &["Пропозиція A", "Пропозиція B"] },
+            ChartText { title: "Job offers", labels: &["Offer A", "Offer B"] },
+        ),
+        Chart::RecurringCost => (
+            ChartText { title: "Накопичені витрати", labels: &["Роки", "Витрачено", "Якби інвестували"] },
+            ChartText { title: "Cumulative cost", labels: &["Years", "Spent", "If invested"] },
+        ),
+        Chart::BlendedRate => (
+            ChartText { title: "Відсоткові ставки, %", labels: &["Борг", "Середня"] },
+            ChartText { title: "Interest rates, %", labels: &["Debt", "Blended"] },
+        ),
+        Chart::SavingsCompare => (
+            ChartText { title: "Порівняння депозитів", labels: &["Внесено", "Підсумок"] },
+            ChartText { title: "Savings accounts", labels: &["Deposited", "Final balance"] },
+        ),
+        Chart::RoundUp => (
+            ChartText { title: "Заощадження з округлень", labels: &["Роки", "Відкладено", "З інвестуванням"] },
+            ChartText { title: "Round-up savings", labels: &["Years", "Saved", "Invested"] },
+        ),
+        Chart::Affordability => (
+            ChartText { title: "Розподіл доходу", labels: &["Наявні борги", "Іпотека", "Решта"] },
+            ChartText { title: "Monthly income split", labels: &["Existing debt", "Mortgage", "Rest"] },
+        ),
+        Chart::BillSplit => (
+            ChartText { title: "Розподіл рахунку", labels: &["Рахунок", "Чайові", "З людини"] },
+            ChartText { title: "Bill split", labels: &["Bill", "Tip", "Per person"] },
+        ),
+        Chart::RaiseImpact => (
+            ChartText { title: "Вплив підвищення", labels: &["Роки", "Без підвищення", "З підвищенням"] },
+            ChartText { title: "Impact of a raise", labels: &["Years", "Without raise", "With raise"] },
+        ),
+        Chart::Budget => (
+            ChartText { title: "Бюджет 50/30/20", labels: &["Потреби", "Бажання", "Заощадження"] },
+            ChartText { title: "50/30/20 budget", labels: &["Needs", "Wants", "Savings"] },
+        ),
+        Chart::CostOfWaiting => (
+            ChartText { title: "Ціна очікування", labels: &["Почати зараз", "Почати пізніше"] },
+            ChartText { title: "Cost of waiting", labels: &["Start now", "Start later"] },
+        ),
+        Chart::LoanTerms => (
+            ChartText { title: "Строк кредиту, роки", labels: &["Щомісячний платіж", "Переплата"] },
+            ChartText { title: "Loan term, years", labels: &["Monthly payment", "Total interest"] },
+        ),
+    };
+
+    match lang {
+        Lang::Uk => uk,
+        Lang::En => en,
+    }
+}
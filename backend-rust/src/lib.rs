@@ -1,123 +1,881 @@
 use worker::*;
 mod models;
 mod calculators;
+mod charts;
+mod i18n;
+mod raster;
+mod schema;
 
 use models::*;
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::io::Write;
 
+/// Responses smaller than this are sent uncompressed, gzip would not pay off.
+const GZIP_THRESHOLD: usize = 1024;
+
+/// Requests per client and window when `RATE_LIMIT_PER_MINUTE` is not set.
+const DEFAULT_RATE_LIMIT: u32 = 60;
+
+/// Length of a rate limit window, also the shortest TTL KV accepts.
+const RATE_LIMIT_WINDOW_SECS: u64 = 60;
+
+/// Largest accepted request body when `MAX_BODY_BYTES` is not set.
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// How long clients may reuse a calculator result; results only depend on the request.
+const CACHE_MAX_AGE_SECS: u32 = 3600;
+
+/// Most calculations accepted in one batch request.
+const MAX_BATCH_ITEMS: usize = 50;
+
+/// How long a batch result is replayed for retries with the same `Idempotency-Key`.
+const IDEMPOTENCY_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Longest accepted `Idempotency-Key`.
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Every calculator served under `/calculate/`, see `dispatch`.
+const CALCULATORS: &[&str] = &[
+    "hourly-income",
+    "time-value",
+    "investment",
+    "credit",
+    "retirement",
+    "debt-payoff",
+    "debt-strategy",
+    "emergency-fund",
+    "tax",
+    "buy-rent",
+    "savings-rate",
+    "fire",
+    "compound",
+    "freelance-rate",
+    "wage-convert",
+    "refinance",
+    "retirement-monte-carlo",
+    "opportunity-cost",
+    "goal-progress",
+    "subscription",
+    "wage-erosion",
+    "net-pay",
+    "required-savings",
+    "debt-vs-invest",
+    "lump-vs-dca",
+    "inflation",
+    "job-compare",
+    "recurring-cost",
+    "blended-rate",
+    "savings-compare",
+    "round-up",
+    "affordability",
+    "bill-split",
+    "raise-impact",
+    "budget",
+    "cost-of-waiting",
+    "loan-terms",
+];
+
+/// Largest magnitude a result is reported with, a quadrillion: JavaScript
+/// numbers are still exact there and serde_json prints it without an exponent.
+const MAX_RESULT: f64 = 1e15;
+
+/// Calculators simple enough to be driven from a query string.
+const QUERY_CALCULATORS: &[&str] = &["hourly-income", "time-value", "tax"];
+
+/// Route table: the methods a path answers besides OPTIONS, `None` for unknown paths.
+fn allowed_methods(path: &str) -> Option<&'static [&'static str]> {
+    match path {
+        "/health" => Some(&["GET"]),
+        "/schema" => Some(&["GET"]),
+        "/currencies" => Some(&["GET"]),
+        "/calculate/batch" => Some(&["POST"]),
+        _ => {
+            let kind = path.strip_prefix("/calculate/")?;
+            if QUERY_CALCULATORS.contains(&kind) {
+                Some(&["GET", "POST"])
+            } else if CALCULATORS.contains(&kind) {
+                Some(&["POST"])
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Rejects unknown currency codes when the request opted into `strict_currency`.
+fn check_currency<T: CalculatorRequest>(data: T) -> std::result::Result<T, String> {
+    let options = data.options();
+    if options.strict_currency() {
+        calculators::parse_currency(options.currency())?;
+    }
+    Ok(data)
+}
+
+/// Builds calculator params from the query string, so simple calculators can
+/// be linked directly, e.g. `/calculate/tax?income=50000&tax_rate=18&currency=UAH`.
+/// Values are converted by the type `schema` declares for the field, anything
+/// else (or a value that doesn't parse) stays a string for serde to judge.
+fn query_params(req: &Request, kind: &str) -> std::result::Result<serde_json::Value, String> {
+    let url = req.url().map_err(|e| e.to_string())?;
+    let params: serde_json::Map<String, serde_json::Value> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let converted = match schema::request_field_kind(kind, &key) {
+                Some("number") => value.parse::<f64>().ok().filter(|n| n.is_finite()).map(|n| serde_json::json!(n)),
+                Some("integer") => value.parse::<u64>().ok().map(|n| serde_json::json!(n)),
+                Some("boolean") => value.parse::<bool>().ok().map(serde_json::Value::Bool),
+                _ => None,
+            };
+            let value = converted.unwrap_or_else(|| serde_json::Value::String(value.into_owned()));
+            (key.into_owned(), value)
+        })
+        .collect();
+    Ok(serde_json::Value::Object(params))
+}
+
+/// Deserializes `params` into the calculator's request type and checks it.
+fn parse<T>(params: serde_json::Value) -> std::result::Result<T, String>
+where
+    T: DeserializeOwned + CalculatorRequest + Validate,
+{
+    let data: T = check_currency(serde_json::from_value(params).map_err(|e| e.to_string())?)?;
+    let violations = data.validate();
+    if !violations.is_empty() {
+        return Err(violations.join("; "));
+    }
+    Ok(data)
+}
+
+/// Caps every number in `value` at `MAX_RESULT`, returning whether any was capped.
+fn clamp_results(value: &mut serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Number(n) => match n.as_f64() {
+            Some(x) if x.abs() > MAX_RESULT => {
+                *value = serde_json::json!(MAX_RESULT.copysign(x));
+                true
+            },
+            _ => false,
+        },
+        serde_json::Value::Array(items) => items.iter_mut().fold(false, |capped, item| clamp_results(item) | capped),
+        serde_json::Value::Object(fields) => fields.values_mut().fold(false, |capped, field| clamp_results(field) | capped),
+        _ => false,
+    }
+}
+
+/// Applies a requested `chart_width`/`chart_height` to every SVG chart of a
+/// calculator response (`chart`, `growth_chart`, ...).
+fn resize_charts(value: &mut serde_json::Value, width: Option<u32>, height: Option<u32>) {
+    if let Some(fields) = value.as_object_mut() {
+        for (key, field) in fields.iter_mut() {
+            if key.ends_with("chart")
+                && let Some(svg) = field.as_str().filter(|svg| !svg.is_empty())
+            {
+                *field = charts::resize(svg, width, height).into();
+            }
+        }
+    }
+}
+
+/// Replaces every SVG chart of a calculator response (`chart`, `growth_chart`, ...)
+/// with a PNG data URL.
+fn rasterize_charts(value: &mut serde_json::Value) -> std::result::Result<(), String> {
+    if let Some(fields) = value.as_object_mut() {
+        for (key, field) in fields.iter_mut() {
+            if key.ends_with("chart")
+                && let Some(svg) = field.as_str().filter(|svg| !svg.is_empty())
+            {
+                *field = raster::png_data_url(svg)?.into();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Deserializes `params` into the calculator's request type, runs it and
+/// renders its chart unless the request opted out with `include_chart: false`.
+/// With `include_series: true` the numbers behind the chart come back as `series`.
+fn run<T, R>(
+    params: serde_json::Value,
+    calculate: impl FnOnce(&T) -> std::result::Result<R, String>,
+    render_chart: impl FnOnce(&T, &mut R),
+) -> std::result::Result<serde_json::Value, String>
+where
+    T: DeserializeOwned + CalculatorRequest + Validate,
+    R: Serialize + PrimaryResult,
+{
+    let data: T = parse(params)?;
+    let options = data.options();
+    let png = match options.chart_format() {
+        None | Some("svg") => false,
+        Some("png") => true,
+        Some(other) => return Err(format!("Unknown chart_format: {} (expected svg or png)", other)),
+    };
+    let conversion = options.display_currency().map(|to| {
+        (options.currency().to_string(), to.to_string(), options.rates().cloned().unwrap_or_default())
+    });
+
+    let mut result = calculate(&data)?;
+    let mut series = None;
+    if options.include_chart() {
+        if options.include_series() {
+            series = Some(charts::recording_series(|| render_chart(&data, &mut result)));
+        } else {
+            render_chart(&data, &mut result);
+        }
+    }
+    let mut value = serde_json::to_value(&result).map_err(|e| e.to_string())?;
+    if let Some(series) = series {
+        value["series"] = serde_json::to_value(series).map_err(|e| e.to_string())?;
+    }
+    let (width, height) = options.chart_size();
+    if width.is_some() || height.is_some() {
+        resize_charts(&mut value, width, height);
+    }
+    if png {
+        rasterize_charts(&mut value)?;
+    }
+    // Extreme inputs would otherwise reach the client as e.g. 4.85e+25
+    let overflow = clamp_results(&mut value);
+    if overflow {
+        value["overflow"] = true.into();
+    }
+    let primary = result.primary_result().clamp(-MAX_RESULT, MAX_RESULT);
+    let formatted = match result.primary_kind() {
+        PrimaryKind::Money => calculators::format_money(primary, options.currency()),
+        PrimaryKind::Percent => format!("{}%", primary),
+    };
+    value[format!("formatted_{}", result.primary_field())] = formatted.into();
+
+    // Only money has a value in another currency
+    if let Some((from, to, rates)) = conversion.filter(|_| result.primary_kind() == PrimaryKind::Money) {
+        let amount = calculators::convert(primary, &from, &to, &rates)?;
+        value["display"] = serde_json::to_value(ConvertedAmount {
+            amount: calculators::round_to(amount, calculators::money_precision(None, &to)),
+            currency_symbol: calculators::get_currency_symbol(&to),
+            currency: to,
+        })
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(value)
+}
+
+/// Runs the calculator named `kind` (the path segment after `/calculate/`).
+/// Returns `None` for unknown calculators.
+///
+/// A panic can't be turned into an error response: the wasm32 target aborts
+/// instead of unwinding. Validation is the guard instead, every term, age and
+/// count that sizes a loop or an allocation is capped before a calculator runs.
+fn dispatch(kind: &str, params: serde_json::Value) -> Option<std::result::Result<serde_json::Value, String>> {
+    let result = match kind {
+        "hourly-income" => run(params, calculators::calculate_hourly_income, charts::hourly_income),
+        "time-value" => run(params, calculators::calculate_time_value, charts::time_value),
+        "investment" => run(params, calculators::calculate_investment, charts::investment),
+        "credit" => run(params, calculators::calculate_credit, charts::credit),
+        "retirement" => run(params, |d| Ok(calculators::calculate_retirement(d)), charts::retirement),
+        "debt-payoff" => run(params, calculators::calculate_debt_payoff, charts::debt_payoff),
+        "debt-strategy" => run(params, calculators::calculate_debt_strategy, charts::debt_strategy),
+        "emergency-fund" => run(params, calculators::calculate_emergency_fund, charts::emergency_fund),
+        "tax" => run(params, |d| Ok(calculators::calculate_tax(d)), charts::tax),
+        "buy-rent" => run(params, calculators::calculate_buy_rent, charts::buy_rent),
+        "savings-rate" => run(params, calculators::calculate_savings_rate, charts::savings_rate),
+        "fire" => run(params, |d| Ok(calculators::calculate_fire(d)), charts::fire),
+        "compound" => run(params, calculators::calculate_compound, charts::compound),
+        "freelance-rate" => run(params, calculators::calculate_freelance_rate, charts::freelance_rate),
+        "wage-convert" => run(params, calculators::calculate_wage_convert, charts::wage_convert),
+        "refinance" => run(params, |d| Ok(calculators::calculate_refinance(d)), charts::refinance),
+        "retirement-monte-carlo" => run(
+            params,
+            |d| Ok(calculators::calculate_retirement_monte_carlo(d)),
+            charts::retirement_monte_carlo,
+        ),
+        "opportunity-cost" => run(
+            params,
+            |d| Ok(calculators::calculate_opportunity_cost(d)),
+            charts::opportunity_cost,
+        ),
+        "goal-progress" => run(params, |d| Ok(calculators::calculate_goal_progress(d)), charts::goal_progress),
+        "subscription" => run(params, calculators::calculate_subscription, charts::subscription),
+        "wage-erosion" => run(params, |d| Ok(calculators::calculate_wage_erosion(d)), charts::wage_erosion),
+        "net-pay" => run(params, |d| Ok(calculators::calculate_net_pay(d)), charts::net_pay),
+        "required-savings" => run(
+            params,
+            |d| Ok(calculators::calculate_required_savings(d)),
+            charts::required_savings,
+        ),
+        "debt-vs-invest" => run(params, |d| Ok(calculators::calculate_debt_vs_invest(d)), charts::debt_vs_invest),
+        "lump-vs-dca" => run(params, |d| Ok(calculators::calculate_lump_vs_dca(d)), charts::lump_vs_dca),
+        "inflation" => run(params, calculators::calculate_inflation, charts::inflation),
+        "job-compare" => run(params, calculators::calculate_job_compare, charts::job_compare),
+        "recurring-cost" => run(params, calculators::calculate_recurring_cost, charts::recurring_cost),
+        "blended-rate" => run(params, |d| Ok(calculators::calculate_blended_rate(d)), charts::blended_rate),
+        "savings-compare" => run(params, |d| Ok(calculators::calculate_savings_compare(d)), charts::savings_compare),
+        "round-up" => run(params, |d| Ok(calculators::calculate_round_up(d)), charts::round_up),
+        "affordability" => run(params, |d| Ok(calculators::calculate_affordability(d)), charts::affordability),
+        "bill-split" => run(params, |d| Ok(calculators::calculate_bill_split(d)), charts::bill_split),
+        "raise-impact" => run(params, calculators::calculate_raise_impact, charts::raise_impact),
+        "budget" => run(params, calculators::calculate_budget, charts::budget),
+        "cost-of-waiting" => run(params, |d| Ok(calculators::calculate_cost_of_waiting(d)), charts::cost_of_waiting),
+        "loan-terms" => run(params, calculators::calculate_loan_terms, charts::loan_terms),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Runs the credit calculator and returns its amortization schedule as CSV.
+fn credit_csv(params: serde_json::Value) -> std::result::Result<String, String> {
+    let mut data: CreditRequest = parse(params)?;
+    data.include_schedule = Some(true);
+
+    let mut csv = String::from("month,payment,principal,interest,remaining_balance\n");
+    for row in calculators::calculate_credit(&data)?.schedule.unwrap_or_default() {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            row.month, row.payment, row.principal, row.interest, row.remaining_balance
+        ));
+    }
+    Ok(csv)
+}
+
+/// Runs every item of a batch, reporting failures per item instead of failing the batch.
+fn run_batch(items: Vec<BatchItem>) -> Vec<serde_json::Value> {
+    items
+        .into_iter()
+        .map(|item| {
+            let error = match dispatch(&item.kind, item.params) {
+                Some(Ok(result)) => return result,
+                Some(Err(e)) => ApiError::bad_request(e),
+                None => ApiError::new(
+                    "BAD_REQUEST",
+                    format!("Unknown calculator type: {}", item.kind),
+                    Some("type".to_string()),
+                ),
+            };
+            serde_json::to_value(error).unwrap_or_default()
+        })
+        .collect()
+}
+
+fn accepts_gzip(req: &Request) -> bool {
+    match req.headers().get("Accept-Encoding") {
+        Ok(Some(encodings)) => encodings
+            .split(',')
+            .any(|e| e.split(';').next().unwrap_or("").trim().eq_ignore_ascii_case("gzip")),
+        _ => false,
+    }
+}
+
+/// CSV is opted into with `Accept: text/csv` or `?format=csv`; JSON stays the default.
+fn wants_csv(req: &Request) -> bool {
+    let accept = matches!(req.headers().get("Accept"), Ok(Some(accept)) if accept.contains("text/csv"));
+    let query = req
+        .url()
+        .map(|url| url.query_pairs().any(|(k, v)| k == "format" && v == "csv"))
+        .unwrap_or(false);
+    accept || query
+}
+
+/// Headless clients ask for the numbers alone with `X-Response-Mode: data` or
+/// `?chart=false`; no chart is rendered then and `chart` comes back empty.
+fn wants_data_only(req: &Request) -> bool {
+    let header = matches!(req.headers().get("X-Response-Mode"), Ok(Some(mode)) if mode.trim().eq_ignore_ascii_case("data"));
+    let query = req
+        .url()
+        .map(|url| url.query_pairs().any(|(k, v)| k == "chart" && v == "false"))
+        .unwrap_or(false);
+    header || query
+}
+
+/// Overrides the request's own `include_chart` for data-only responses.
+fn skip_charts(params: &mut serde_json::Value) {
+    if let Some(fields) = params.as_object_mut() {
+        fields.insert("include_chart".to_string(), false.into());
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across deploys unlike std's hasher.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Weak ETag over the JSON body. Weak because the same JSON may go out gzipped or not.
+fn etag(body: &str) -> String {
+    format!("W/\"{:016x}\"", fnv1a(body))
+}
+
+/// Whether the client's `If-None-Match` already holds `etag`.
+fn not_modified(req: &Request, etag: &str) -> bool {
+    let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    match req.headers().get("If-None-Match") {
+        Ok(Some(tags)) => tags.split(',').any(|tag| tag.trim() == "*" || strip(tag) == strip(etag)),
+        _ => false,
+    }
+}
+
+/// Runs `f` and returns its result with the milliseconds it took. Workers only
+/// advance the clock across I/O, so pure computation often measures as 0.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let started = Date::now().as_millis();
+    let result = f();
+    (result, Date::now().as_millis().saturating_sub(started))
+}
+
+fn gzip(body: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body)?;
+    encoder.finish()
+}
+
+/// Counts the request against its client's current window in the `RATE_LIMIT`
+/// KV namespace and returns the seconds until the window resets once the limit
+/// is exceeded. KV is eventually consistent, so the limit is approximate.
+/// Without the binding (e.g. local dev) nothing is limited.
+async fn rate_limited(req: &Request, env: &Env) -> Result<Option<u64>> {
+    let kv = match env.kv("RATE_LIMIT") {
+        Ok(kv) => kv,
+        Err(_) => return Ok(None),
+    };
+    let limit = env
+        .var("RATE_LIMIT_PER_MINUTE")
+        .ok()
+        .and_then(|v| v.to_string().parse().ok())
+        .unwrap_or(DEFAULT_RATE_LIMIT);
+    let client = req.headers().get("CF-Connecting-IP")?.unwrap_or_else(|| "unknown".to_string());
+
+    let now = Date::now().as_millis() / 1000;
+    let key = format!("rate:{}:{}", client, now / RATE_LIMIT_WINDOW_SECS);
+    let count = kv
+        .get(&key)
+        .text()
+        .await?
+        .and_then(|c| c.parse::<u32>().ok())
+        .unwrap_or(0)
+        + 1;
+    if count > limit {
+        return Ok(Some(RATE_LIMIT_WINDOW_SECS - now % RATE_LIMIT_WINDOW_SECS));
+    }
+
+    kv.put(&key, count.to_string())?
+        .expiration_ttl(RATE_LIMIT_WINDOW_SECS * 2)
+        .execute()
+        .await?;
+    Ok(None)
+}
+
+/// A batch result kept under its `Idempotency-Key`, with a hash of the batch
+/// it answered so the key can't replay it for a different one.
+#[derive(Serialize, Deserialize)]
+struct StoredBatch {
+    request: String,
+    results: serde_json::Value,
+}
+
+/// The client's `Idempotency-Key`: `Ok(None)` without one, an error message when
+/// it is not short printable ASCII.
+fn idempotency_key(req: &Request) -> std::result::Result<Option<String>, String> {
+    match req.headers().get("Idempotency-Key") {
+        Ok(Some(key)) if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN || !key.bytes().all(|b| b.is_ascii_graphic()) => {
+            Err(format!("Idempotency-Key must be 1 to {} printable ASCII characters", MAX_IDEMPOTENCY_KEY_LEN))
+        },
+        Ok(key) => Ok(key),
+        Err(_) => Ok(None),
+    }
+}
+
+/// The batch result stored under `key` in the `IDEMPOTENCY` KV namespace, if
+/// any. Without the binding (e.g. local dev) nothing is stored or replayed.
+async fn stored_batch(env: &Env, key: &str) -> Result<Option<StoredBatch>> {
+    let kv = match env.kv("IDEMPOTENCY") {
+        Ok(kv) => kv,
+        Err(_) => return Ok(None),
+    };
+    let stored = kv.get(&format!("batch:{}", key)).text().await?;
+    Ok(stored.and_then(|json| serde_json::from_str(&json).ok()))
+}
+
+async fn store_batch(env: &Env, key: &str, batch: &StoredBatch) -> Result<()> {
+    let kv = match env.kv("IDEMPOTENCY") {
+        Ok(kv) => kv,
+        Err(_) => return Ok(()),
+    };
+    let json = serde_json::to_string(batch).map_err(|e| worker::Error::from(e.to_string()))?;
+    kv.put(&format!("batch:{}", key), json)?
+        .expiration_ttl(IDEMPOTENCY_TTL_SECS)
+        .execute()
+        .await?;
+    Ok(())
+}
+
+/// `Access-Control-Allow-Origin` for this request. `ALLOWED_ORIGINS` holds a
+/// comma-separated allow-list: a listed `Origin` is echoed back, any other gets
+/// no header. Without the variable every origin is allowed with `*`.
+fn cors_origin(req: &Request, env: &Env) -> Option<String> {
+    let allowed = match env.var("ALLOWED_ORIGINS") {
+        Ok(v) if !v.to_string().trim().is_empty() => v.to_string(),
+        _ => return Some("*".to_string()),
+    };
+    let origin = req.headers().get("Origin").ok().flatten()?;
+    allowed.split(',').any(|o| o.trim() == origin).then_some(origin)
+}
+
+/// An echoed origin makes the response depend on the request's `Origin`, so
+/// caches are told with `Vary`.
+fn set_cors_origin(headers: &mut Headers, origin: Option<&str>) -> Result<()> {
+    match origin {
+        Some("*") => headers.set("Access-Control-Allow-Origin", "*"),
+        Some(origin) => {
+            headers.set("Access-Control-Allow-Origin", origin)?;
+            headers.append("Vary", "Origin")
+        },
+        None => Ok(()),
+    }
+}
+
+/// Headers every response leaves with, errors included: the CORS origin, so
+/// pages on another origin can read error messages too, a JSON Content-Type
+/// unless the handler picked another, and the length of a fixed body.
+fn finish_response(response: &mut Response, cors: Option<&str>) -> Result<()> {
+    let length = match response.body() {
+        ResponseBody::Body(bytes) => Some(bytes.len()),
+        _ => None,
+    };
+    let headers = response.headers_mut();
+    set_cors_origin(headers, cors)?;
+    if let Some(length) = length.filter(|&length| length > 0) {
+        if !headers.has("Content-Type")? {
+            headers.set("Content-Type", "application/json")?;
+        }
+        headers.set("Content-Length", &length.to_string())?;
+    }
+    Ok(())
+}
+
+fn payload_too_large(message: String) -> (u16, ApiError) {
+    (413, ApiError::new("PAYLOAD_TOO_LARGE", message, None))
+}
+
+/// Reads a JSON body of at most `limit` bytes. A too large `Content-Length` is
+/// refused before reading anything; the length is checked again after reading
+/// for bodies sent without one. Empty bodies get a message of their own.
+/// Nesting depth is bounded by serde_json's recursion limit.
+async fn read_json<T: DeserializeOwned>(req: &mut Request, limit: usize) -> std::result::Result<T, (u16, ApiError)> {
+    let too_large = || payload_too_large(format!("Request body exceeds {} bytes", limit));
+    let declared = req
+        .headers()
+        .get("Content-Length")
+        .ok()
+        .flatten()
+        .and_then(|len| len.parse::<usize>().ok());
+    if declared.is_some_and(|len| len > limit) {
+        return Err(too_large());
+    }
+
+    let body = req.bytes().await.map_err(|e| (400, ApiError::bad_request(e.to_string())))?;
+    if body.len() > limit {
+        return Err(too_large());
+    }
+    // An empty body would otherwise surface as serde's "EOF while parsing a value"
+    if body.iter().all(u8::is_ascii_whitespace) {
+        let message = format!("Request body required for {}, send JSON with Content-Type: application/json", req.path());
+        return Err((400, ApiError::bad_request(message)));
+    }
+    serde_json::from_slice(&body).map_err(|e| (400, ApiError::bad_request(e.to_string())))
+}
+
+fn error_response(status: u16, error: ApiError) -> Result<Response> {
+    Ok(Response::from_json(&error)?.with_status(status))
+}
+
+/// Longest client-supplied `X-Request-ID` echoed back as is.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// The client's `X-Request-ID` when it is short printable ASCII, otherwise a
+/// fresh random UUID (version 4).
+fn request_id(req: &Request) -> String {
+    if let Ok(Some(id)) = req.headers().get("X-Request-ID")
+        && !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_graphic())
+    {
+        return id;
+    }
+
+    let mut bytes = [0u8; 16];
+    for byte in bytes.iter_mut() {
+        *byte = (js_sys::Math::random() * 256.0) as u8;
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// One log line per request. Only metadata goes in: bodies and query strings
+/// can carry users' financial details and are never logged.
+#[derive(Serialize)]
+struct RequestLog<'a> {
+    request_id: &'a str,
+    method: &'a str,
+    path: &'a str,
+    status: u16,
+    duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<&'a str>,
+}
+
+/// Formats a request as a single JSON line, e.g.
+/// `{"request_id":"...","method":"POST","path":"/calculate/tax","status":400,"duration_ms":3,"error_code":"BAD_REQUEST"}`.
+fn log_line(entry: &RequestLog) -> String {
+    serde_json::to_string(entry).unwrap_or_default()
+}
+
+/// `error.code` of an error response, read from a copy so the body is still sent.
+async fn error_code(response: &mut Response) -> Option<String> {
+    let body: serde_json::Value = response.cloned().ok()?.json().await.ok()?;
+    body["error"]["code"].as_str().map(str::to_string)
+}
+
+/// Tags every response, errors included, with the request's `X-Request-ID`
+/// and the common headers of `finish_response`, and logs each request under
+/// the id, so client reports can be matched to the logs.
 #[event(fetch)]
-async fn main(mut req: Request, _env: Env, _ctx: Context) -> Result<Response> {
+async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     console_error_panic_hook::set_once();
-    
+
+    let started = Date::now().as_millis();
+    let id = request_id(&req);
+    let method = req.method();
+    let path = req.path();
+    let cors = cors_origin(&req, &env);
+    let mut response = match handle(req, env).await {
+        Ok(response) => response,
+        // e.g. KV being unreachable; still a JSON error the page can read
+        Err(e) => {
+            console_error!("{}", e);
+            error_response(500, ApiError::internal(&path))?
+        },
+    };
+    finish_response(&mut response, cors.as_deref())?;
+    let status = response.status_code();
+    let error_code = if status >= 400 { error_code(&mut response).await } else { None };
+    console_log!("{}", log_line(&RequestLog {
+        request_id: &id,
+        method: method.as_ref(),
+        path: &path,
+        status,
+        duration_ms: Date::now().as_millis().saturating_sub(started),
+        error_code: error_code.as_deref(),
+    }));
+    response.headers_mut().set("X-Request-ID", &id)?;
+    response.headers_mut().append("Access-Control-Expose-Headers", "X-Request-ID")?;
+    Ok(response)
+}
+
+async fn handle(mut req: Request, env: Env) -> Result<Response> {
     let path = req.path();
     let method = req.method();
 
-    // CORS handling for all endpoints
+    // CORS preflight for all endpoints, the origin is added by `finish_response`
     if method == Method::Options {
          let mut headers = Headers::new();
-         headers.set("Access-Control-Allow-Origin", "*")?;
          headers.set("Access-Control-Allow-Methods", "GET, POST, OPTIONS")?;
-         headers.set("Access-Control-Allow-Headers", "Content-Type")?;
+         headers.set("Access-Control-Allow-Headers", "Content-Type, If-None-Match, X-Request-ID, X-Response-Mode, Idempotency-Key")?;
          return Ok(Response::empty()?.with_headers(headers));
     }
 
-    // Health check
-    if method == Method::Get && path == "/health" {
-        return Response::ok("OK");
+    match allowed_methods(&path) {
+        None => return error_response(404, ApiError::not_found(&path)),
+        Some(methods) if !methods.contains(&method.as_ref()) => {
+            let error = ApiError::new(
+                "METHOD_NOT_ALLOWED",
+                format!("{} is not supported on {}", method.as_ref(), path),
+                None,
+            );
+            let mut response = error_response(405, error)?;
+            response.headers_mut().set("Allow", &format!("{}, OPTIONS", methods.join(", ")))?;
+            return Ok(response);
+        },
+        Some(_) => {},
     }
 
-    // Calculator Endpoints
-    if method == Method::Post {
-        let mut headers = Headers::new();
-        headers.set("Content-Type", "application/json")?;
-        headers.set("Access-Control-Allow-Origin", "*")?;
+    // Health check, `?format=text` keeps the plain "OK" body
+    if path == "/health" {
+        let plain = req.url()?.query_pairs().any(|(k, v)| k == "format" && v == "text");
+        if plain {
+            return Response::ok("OK");
+        }
+        return Response::from_json(&HealthResponse {
+            status: "ok",
+            version: env!("CARGO_PKG_VERSION"),
+            // Set by the wrangler build command
+            commit: option_env!("GIT_COMMIT").filter(|c| !c.is_empty()).unwrap_or("unknown"),
+        });
+    }
 
-        match path.as_str() {
-            "/calculate/hourly-income" => {
-                let data: HourlyIncomeRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_hourly_income(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/time-value" => {
-                let data: TimeValueRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_time_value(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/investment" => {
-                let data: InvestmentRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_investment(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/credit" => {
-                let data: CreditRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_credit(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/retirement" => {
-                let data: RetirementRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_retirement(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/debt-payoff" => {
-                let data: DebtPayoffRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_debt_payoff(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/emergency-fund" => {
-                let data: EmergencyFundRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_emergency_fund(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/tax" => {
-                let data: TaxRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
-                };
-                let result = calculators::calculate_tax(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            "/calculate/buy-rent" => {
-                let data: BuyRentRequest = match req.json().await {
-                    Ok(d) => d,
-                    Err(e) => return Response::error(format!("Bad Request: {}", e), 400),
+    // Request and response fields of every calculator
+    if path == "/schema" {
+        return Response::from_json(&schema::schema());
+    }
+
+    // Supported currencies, from the same table the calculators format with
+    if path == "/currencies" {
+        let currencies: Vec<CurrencyInfo> = calculators::CURRENCIES
+            .iter()
+            .map(|c| CurrencyInfo {
+                code: c.code,
+                symbol: c.symbol,
+                symbol_position: if c.symbol_after { "after" } else { "before" },
+                precision: c.precision,
+            })
+            .collect();
+        return Response::from_json(&currencies);
+    }
+
+    let kind = match path.strip_prefix("/calculate/") {
+        Some(kind) => kind.to_string(),
+        None => return error_response(404, ApiError::not_found(&path)),
+    };
+
+    // Development hook for the 500 path, e.g. `wrangler dev --var FAIL_CALCULATOR:tax`
+    if env.var("FAIL_CALCULATOR").is_ok_and(|v| v.to_string() == kind) {
+        return Err(worker::Error::from(format!("FAIL_CALCULATOR is set to {}", kind)));
+    }
+
+    if let Some(retry_after) = rate_limited(&req, &env).await? {
+        let error = ApiError::new(
+            "RATE_LIMITED",
+            format!("Too many requests, retry in {} seconds", retry_after),
+            None,
+        );
+        let mut response = error_response(429, error)?;
+        response.headers_mut().set("Retry-After", &retry_after.to_string())?;
+        return Ok(response);
+    }
+
+    let max_body_bytes = env
+        .var("MAX_BODY_BYTES")
+        .ok()
+        .and_then(|v| v.to_string().parse().ok())
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES);
+
+    let mut headers = Headers::new();
+    headers.set("Content-Type", "application/json")?;
+
+    let data_only = wants_data_only(&req);
+    let mut calc_ms = 0;
+    let result = match method {
+        // Query-string variants of the simpler calculators
+        Method::Get if QUERY_CALCULATORS.contains(&kind.as_str()) => {
+            let mut params = match query_params(&req, &kind) {
+                Ok(p) => p,
+                Err(e) => return error_response(400, ApiError::bad_request(e)),
+            };
+            if data_only {
+                skip_charts(&mut params);
+            }
+            let (result, ms) = timed(|| dispatch(&kind, params));
+            calc_ms = ms;
+            result
+        },
+        Method::Post if kind == "batch" => {
+            let mut items: Vec<BatchItem> = match read_json(&mut req, max_body_bytes).await {
+                Ok(d) => d,
+                Err((status, error)) => return error_response(status, error),
+            };
+            if items.len() > MAX_BATCH_ITEMS {
+                let (status, error) =
+                    payload_too_large(format!("A batch holds at most {} calculations", MAX_BATCH_ITEMS));
+                return error_response(status, error);
+            }
+            if data_only {
+                items.iter_mut().for_each(|item| skip_charts(&mut item.params));
+            }
+            let key = match idempotency_key(&req) {
+                Ok(key) => key,
+                Err(e) => return error_response(400, ApiError::bad_request(e)),
+            };
+            // Parsed and re-serialized, so formatting and key order don't change the hash
+            let request = format!("{:016x}", fnv1a(&serde_json::to_string(&items).unwrap_or_default()));
+            let stored = match &key {
+                Some(key) => stored_batch(&env, key).await?,
+                None => None,
+            };
+            match stored {
+                Some(stored) if stored.request == request => {
+                    headers.set("Idempotent-Replayed", "true")?;
+                    Some(Ok(stored.results))
+                },
+                Some(_) => {
+                    let error = ApiError::new(
+                        "IDEMPOTENCY_KEY_REUSED",
+                        "Idempotency-Key was already used for a different batch",
+                        None,
+                    );
+                    return error_response(422, error);
+                },
+                None => {
+                    let (results, ms) = timed(|| run_batch(items));
+                    calc_ms = ms;
+                    let results = serde_json::Value::Array(results);
+                    if let Some(key) = &key {
+                        store_batch(&env, key, &StoredBatch { request, results: results.clone() }).await?;
+                    }
+                    Some(Ok(results))
+                },
+            }
+        },
+        Method::Post => {
+            let mut params: serde_json::Value = match read_json(&mut req, max_body_bytes).await {
+                Ok(d) => d,
+                Err((status, error)) => return error_response(status, error),
+            };
+            if data_only {
+                skip_charts(&mut params);
+            }
+            if kind == "credit" && wants_csv(&req) {
+                let csv = match credit_csv(params) {
+                    Ok(csv) => csv,
+                    Err(e) => return error_response(400, ApiError::bad_request(e)),
                 };
-                let result = calculators::calculate_buy_rent(data);
-                let json = serde_json::to_string(&result).map_err(|e| worker::Error::from(e.to_string()))?;
-                return Ok(Response::ok(json)?.with_headers(headers));
-            },
-            _ => {
-                return Response::error("Not Found", 404);
+                headers.set("Content-Type", "text/csv; charset=utf-8")?;
+                headers.set("Content-Disposition", "attachment; filename=\"credit-schedule.csv\"")?;
+                return Ok(Response::ok(csv)?.with_headers(headers));
             }
-        }
-    }
+            let (result, ms) = timed(|| dispatch(&kind, params));
+            calc_ms = ms;
+            result
+        },
+        _ => None,
+    };
 
-    Response::error("Not Found", 404)
+    match result {
+        Some(Ok(result)) => {
+            let (json, serialize_ms) = timed(|| serde_json::to_string(&result));
+            let json = json.map_err(|e| worker::Error::from(e.to_string()))?;
+            let etag = etag(&json);
+            headers.append("Vary", "Accept-Encoding, X-Response-Mode")?;
+            headers.set("ETag", &etag)?;
+            headers.set("Cache-Control", &format!("public, max-age={}", CACHE_MAX_AGE_SECS))?;
+            headers.set("Server-Timing", &format!("calc;dur={}, serialize;dur={}", calc_ms, serialize_ms))?;
+            headers.set("Access-Control-Expose-Headers", "ETag, Server-Timing, Idempotent-Replayed")?;
+            if not_modified(&req, &etag) {
+                return Ok(Response::empty()?.with_status(304).with_headers(headers));
+            }
+            if json.len() >= GZIP_THRESHOLD && accepts_gzip(&req) {
+                let body = gzip(json.as_bytes()).map_err(|e| worker::Error::from(e.to_string()))?;
+                headers.set("Content-Encoding", "gzip")?;
+                // Already compressed, the runtime must pass the body through as-is
+                return Ok(Response::builder()
+                    .with_headers(headers)
+                    .with_encode_body(EncodeBody::Manual)
+                    .fixed(body));
+            }
+            Ok(Response::ok(json)?.with_headers(headers))
+        },
+        Some(Err(e)) => error_response(400, ApiError::bad_request(e)),
+        None => error_response(404, ApiError::not_found(&path)),
+    }
 }
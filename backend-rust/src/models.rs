@@ -1,4 +1,165 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Currency settings shared by every calculator request.
+pub trait CurrencyOptions {
+    fn currency(&self) -> &str;
+    fn strict_currency(&self) -> bool;
+    fn rates(&self) -> Option<&HashMap<String, f64>>;
+    fn display_currency(&self) -> Option<&str>;
+}
+
+/// Whether the router should render the SVG chart after the calculation.
+pub trait ChartOptions {
+    fn include_chart(&self) -> bool;
+    /// "svg" (default) or "png".
+    fn chart_format(&self) -> Option<&str>;
+    /// Requested chart size in pixels; either may be left out.
+    fn chart_size(&self) -> (Option<u32>, Option<u32>);
+    /// Also return the label/value pairs the chart was drawn from.
+    fn include_series(&self) -> bool;
+}
+
+/// Domain checks run before a calculator, returning every violation found.
+pub trait Validate {
+    fn validate(&self) -> Vec<String>;
+}
+
+fn check_non_negative(errors: &mut Vec<String>, field: &str, value: f64) {
+    if value < 0.0 {
+        errors.push(format!("{} must not be negative", field));
+    }
+}
+
+fn check_range(errors: &mut Vec<String>, field: &str, value: f64, min: f64, max: f64) {
+    if value < min || value > max {
+        errors.push(format!("{} must be between {} and {}", field, min, max));
+    }
+}
+
+/// Oldest age a calculator accepts.
+const MAX_AGE: f64 = 120.0;
+
+/// Most loan terms compared at once, each one builds a full schedule.
+const MAX_LOAN_TERMS: usize = 10;
+
+/// Most savings accounts compared at once, each one is a group in the chart.
+const MAX_SAVINGS_ACCOUNTS: usize = 10;
+
+/// Tax and interest rates, in percent.
+fn check_rate(errors: &mut Vec<String>, field: &str, value: f64) {
+    check_range(errors, field, value, 0.0, 100.0);
+}
+
+/// Growth, return and inflation rates, in percent; these may be negative.
+fn check_growth(errors: &mut Vec<String>, field: &str, value: f64) {
+    check_range(errors, field, value, -100.0, 100.0);
+}
+
+/// How a headline figure is formatted.
+#[derive(Clone, Copy, PartialEq)]
+pub enum PrimaryKind {
+    /// In the request's currency, also echoed in `display_currency`.
+    Money,
+    Percent,
+}
+
+/// The headline figure of a calculator response.
+pub trait PrimaryResult {
+    fn primary_result(&self) -> f64;
+    /// Name of the headline field, for its `formatted_*` companion.
+    fn primary_field(&self) -> &'static str;
+    fn primary_kind(&self) -> PrimaryKind;
+}
+
+/// Options accepted by every calculator request, flattened into its JSON.
+#[derive(Deserialize, Default)]
+pub struct CommonOptions {
+    pub currency: String,
+    pub strict_currency: Option<bool>,
+    pub rates: Option<HashMap<String, f64>>,
+    pub display_currency: Option<String>,
+    pub precision: Option<u8>,
+    pub lang: Option<String>,
+    pub include_chart: Option<bool>,
+    pub theme: Option<String>,
+    pub palette: Option<String>,
+    pub chart_format: Option<String>,
+    pub chart_width: Option<u32>,
+    pub chart_height: Option<u32>,
+    pub include_series: Option<bool>,
+}
+
+impl CurrencyOptions for CommonOptions {
+    fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    fn strict_currency(&self) -> bool {
+        self.strict_currency.unwrap_or(false)
+    }
+
+    fn rates(&self) -> Option<&HashMap<String, f64>> {
+        self.rates.as_ref()
+    }
+
+    fn display_currency(&self) -> Option<&str> {
+        self.display_currency.as_deref()
+    }
+}
+
+impl ChartOptions for CommonOptions {
+    fn include_chart(&self) -> bool {
+        self.include_chart.unwrap_or(true)
+    }
+
+    fn chart_format(&self) -> Option<&str> {
+        self.chart_format.as_deref()
+    }
+
+    fn chart_size(&self) -> (Option<u32>, Option<u32>) {
+        (self.chart_width, self.chart_height)
+    }
+
+    fn include_series(&self) -> bool {
+        self.include_series.unwrap_or(false)
+    }
+}
+
+/// Calculator requests, each carrying the `CommonOptions`.
+pub trait CalculatorRequest {
+    fn options(&self) -> &CommonOptions;
+}
+
+macro_rules! impl_request_options {
+    ($($request:ty),* $(,)?) => {
+        $(impl CalculatorRequest for $request {
+            fn options(&self) -> &CommonOptions {
+                &self.options
+            }
+        })*
+    };
+}
+
+macro_rules! impl_primary_result {
+    ($($response:ty => $field:ident $(as $kind:ident)?),* $(,)?) => {
+        $(impl PrimaryResult for $response {
+            fn primary_result(&self) -> f64 {
+                self.$field
+            }
+
+            fn primary_field(&self) -> &'static str {
+                stringify!($field)
+            }
+
+            fn primary_kind(&self) -> PrimaryKind {
+                impl_primary_result!(@kind $($kind)?)
+            }
+        })*
+    };
+    (@kind) => { PrimaryKind::Money };
+    (@kind $kind:ident) => { PrimaryKind::$kind };
+}
 
 #[derive(Deserialize)]
 pub struct HourlyIncomeRequest {
@@ -7,7 +168,8 @@ pub struct HourlyIncomeRequest {
     pub work_hours: f64,
     pub commute_time: f64,
     pub work_expenses: f64,
-    pub currency: String,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
@@ -24,12 +186,16 @@ pub struct HourlyIncomeResponse {
 pub struct TimeValueRequest {
     pub annual_income: f64,
     pub annual_hours: f64,
-    pub currency: String,
+    /// Purchasing power relative to a reference country, 1.0 by default.
+    pub ppp_factor: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
 pub struct TimeValueResponse {
     pub time_value: f64,
+    pub ppp_adjusted_value: f64,
     pub currency_symbol: String,
     pub chart: String,
 }
@@ -39,16 +205,40 @@ pub struct CreditRequest {
     pub amount: f64,
     pub rate: f64,
     pub term: f64,
-    pub currency: String,
+    /// "monthly" (default) or "biweekly", 26 payments a year.
+    pub payment_frequency: Option<String>,
+    pub include_schedule: Option<bool>,
+    pub chart_type: Option<String>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct AmortizationRow {
+    /// Payment number; biweekly loans count fortnights.
+    pub month: u32,
+    pub payment: f64,
+    pub principal: f64,
+    pub interest: f64,
+    pub remaining_balance: f64,
 }
 
 #[derive(Serialize)]
 pub struct CreditResponse {
+    /// For biweekly loans, the biweekly payments averaged per month.
     pub monthly_payment: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biweekly_payment: Option<f64>,
     pub total_payment: f64,
     pub overpayment: f64,
+    pub effective_annual_rate: f64,
+    /// Split of the first payment, the schedule's first row; early payments are mostly interest.
+    pub first_month_interest: f64,
+    pub first_month_principal: f64,
     pub currency_symbol: String,
     pub chart: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Vec<AmortizationRow>>,
 }
 
 #[derive(Deserialize)]
@@ -56,8 +246,19 @@ pub struct InvestmentRequest {
     pub initial_amount: f64,
     pub monthly_contribution: f64,
     pub annual_return: f64,
+    /// Optional pessimistic and optimistic returns for a sensitivity view.
+    pub return_low: Option<f64>,
+    pub return_high: Option<f64>,
+    pub inflation_rate: Option<f64>,
+    /// Years; 0 returns the initial amount unchanged, negative periods are rejected.
     pub period: f64,
-    pub currency: String,
+    pub compounding: Option<String>,
+    /// Contributions at the start of each period rather than the end.
+    pub annuity_due: Option<bool>,
+    pub include_growth_chart: Option<bool>,
+    pub chart_type: Option<String>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
@@ -66,8 +267,24 @@ pub struct InvestmentResponse {
     pub total_contributions: f64,
     pub total_gain: f64,
     pub roi: f64,
+    pub real_future_value: f64,
+    pub real_roi: f64,
     pub currency_symbol: String,
     pub chart: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub growth_chart: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenarios: Option<InvestmentScenarios>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenario_chart: Option<String>,
+}
+
+/// Future values at the low, expected and high return rates.
+#[derive(Serialize)]
+pub struct InvestmentScenarios {
+    pub low: f64,
+    pub expected: f64,
+    pub high: f64,
 }
 
 #[derive(Deserialize)]
@@ -78,14 +295,25 @@ pub struct RetirementRequest {
     pub current_savings: f64,
     pub monthly_savings: f64,
     pub expected_return: f64,
-    pub currency: String,
+    pub inflation_rate: Option<f64>,
+    /// Percent of capital drawn down each year in retirement, 4 by default.
+    pub withdrawal_rate: Option<f64>,
+    /// Savings paid in at the start of each month rather than the end.
+    pub annuity_due: Option<bool>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
 pub struct RetirementResponse {
     pub future_value: f64,
+    /// What current savings and the monthly savings grow into; they add up to `future_value`.
+    pub future_value_savings: f64,
+    pub future_value_contributions: f64,
     pub required_capital: f64,
     pub gap: f64,
+    pub future_value_real: f64,
+    pub required_capital_real: f64,
     pub currency_symbol: String,
     pub chart: String,
 }
@@ -96,7 +324,9 @@ pub struct DebtPayoffRequest {
     pub interest_rate: f64,
     pub monthly_payment: f64,
     pub extra_payment: f64,
-    pub currency: String,
+    pub chart_type: Option<String>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
@@ -104,6 +334,45 @@ pub struct DebtPayoffResponse {
     pub months: u32,
     pub total_paid: f64,
     pub total_interest: f64,
+    /// False only for a payment that exactly covers the interest; anything
+    /// less is refused.
+    pub payable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_payment_required: Option<f64>,
+    pub currency_symbol: String,
+    pub chart: String,
+    /// Balance and cumulative interest over the months to payoff, for payable debts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeline_chart: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct Debt {
+    pub balance: f64,
+    pub rate: f64,
+    pub min_payment: f64,
+}
+
+#[derive(Deserialize)]
+pub struct DebtStrategyRequest {
+    pub debts: Vec<Debt>,
+    pub extra_payment: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct StrategyResult {
+    pub months: u32,
+    pub total_interest: f64,
+}
+
+#[derive(Serialize)]
+pub struct DebtStrategyResponse {
+    pub snowball: StrategyResult,
+    pub avalanche: StrategyResult,
+    pub recommendation: String,
+    pub interest_saved: f64,
     pub currency_symbol: String,
     pub chart: String,
 }
@@ -114,7 +383,8 @@ pub struct EmergencyFundRequest {
     pub months_coverage: f64,
     pub current_savings: f64,
     pub monthly_contribution: f64,
-    pub currency: String,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
@@ -122,15 +392,30 @@ pub struct EmergencyFundResponse {
     pub target_amount: f64,
     pub remaining_amount: f64,
     pub months_to_target: f64,
+    /// Months of expenses the current savings already cover; absent when there are no expenses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_coverage_months: Option<f64>,
     pub currency_symbol: String,
     pub chart: String,
 }
 
+#[derive(Deserialize)]
+pub struct TaxBracket {
+    /// Upper end of the band; the last band also taxes any income above it.
+    pub up_to: Option<f64>,
+    pub rate: f64,
+}
+
 #[derive(Deserialize)]
 pub struct TaxRequest {
     pub income: f64,
     pub tax_rate: f64,
-    pub currency: String,
+    pub brackets: Option<Vec<TaxBracket>>,
+    /// Tax-free amount deducted from `income` before any rate applies.
+    pub allowance: Option<f64>,
+    pub chart_type: Option<String>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
@@ -138,6 +423,9 @@ pub struct TaxResponse {
     pub tax_amount: f64,
     pub net_income: f64,
     pub effective_rate: f64,
+    /// Rate on the last unit of taxable income, from the top band it reaches;
+    /// equal to `tax_rate` in flat mode.
+    pub marginal_rate: f64,
     pub currency_symbol: String,
     pub chart: String,
 }
@@ -152,7 +440,12 @@ pub struct BuyRentRequest {
     pub rent_growth: f64,
     pub property_growth: f64,
     pub horizon: f64,
-    pub currency: String,
+    pub monthly_overpayment: Option<f64>,
+    /// Savings towards `down_payment` so far and added every month.
+    pub current_down_savings: Option<f64>,
+    pub monthly_down_contribution: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
 }
 
 #[derive(Serialize)]
@@ -160,6 +453,1393 @@ pub struct BuyRentResponse {
     pub net_buy_position: f64,
     pub net_rent_position: f64,
     pub recommendation: String,
+    pub total_interest_paid: f64,
+    pub break_even_year: Option<f64>,
+    /// Only with the down payment savings fields; -1.0 when never reached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub months_to_down_payment: Option<f64>,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct SavingsRateRequest {
+    pub monthly_income: f64,
+    pub monthly_expenses: f64,
+    pub expected_return: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct SavingsRateResponse {
+    pub savings_rate: f64,
+    pub annual_savings: f64,
+    pub fi_target: f64,
+    pub years_to_independence: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct FireRequest {
+    pub current_net_worth: f64,
+    pub annual_expenses: f64,
+    pub annual_savings: f64,
+    pub expected_return: f64,
+    pub current_age: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct FireResponse {
+    pub fire_target: f64,
+    /// -1.0 when the target is not reached within 100 years.
+    pub years_to_fire: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fire_age: Option<f64>,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct CompoundRequest {
+    pub principal: f64,
+    pub annual_rate: f64,
+    pub years: f64,
+    pub compounding: Option<String>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct CompoundResponse {
+    pub final_amount: f64,
+    pub interest_earned: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct FreelanceRateRequest {
+    pub desired_net_income: f64,
+    pub billable_hours: f64,
+    pub business_expenses: f64,
+    pub tax_rate: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct FreelanceRateResponse {
+    pub hourly_rate: f64,
+    pub gross_income: f64,
+    pub tax_amount: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct WageConvertRequest {
+    /// Exactly one of `annual_salary` and `hourly_rate` must be given.
+    pub annual_salary: Option<f64>,
+    pub hourly_rate: Option<f64>,
+    pub hours_per_week: f64,
+    pub weeks_per_year: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct WageConvertResponse {
+    pub annual: f64,
+    pub monthly: f64,
+    pub weekly: f64,
+    pub daily: f64,
+    pub hourly: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct RefinanceRequest {
+    pub current_balance: f64,
+    pub current_rate: f64,
+    /// Years left on the current loan.
+    pub remaining_term: f64,
+    pub new_rate: f64,
+    pub new_term: f64,
+    pub closing_costs: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct RefinanceResponse {
+    pub current_monthly_payment: f64,
+    pub new_monthly_payment: f64,
+    pub current_total_interest: f64,
+    pub new_total_interest: f64,
+    /// Interest saved minus closing costs.
+    pub lifetime_savings: f64,
+    /// `None` when the monthly savings never cover the closing costs.
+    pub break_even_month: Option<u32>,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct RetirementMonteCarloRequest {
+    pub current_age: f64,
+    pub retirement_age: f64,
+    pub desired_income: f64,
+    pub current_savings: f64,
+    pub monthly_savings: f64,
+    /// Mean and standard deviation of the yearly return, in percent.
+    pub expected_return: f64,
+    pub volatility: f64,
+    /// Defaults to 500, capped at 1000.
+    pub trials: Option<u32>,
+    /// Same seed, same results.
+    pub seed: Option<u64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct RetirementMonteCarloResponse {
+    /// Share of trials reaching `required_capital`, in percent.
+    pub success_probability: f64,
+    pub required_capital: f64,
+    pub percentile_10: f64,
+    pub percentile_50: f64,
+    pub percentile_90: f64,
+    pub trials: u32,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct OpportunityCostRequest {
+    /// Money spent instead of invested.
+    pub amount: f64,
+    pub annual_return: f64,
+    pub years: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct OpportunityCostResponse {
+    /// What `amount` would have grown into.
+    pub future_value: f64,
+    /// Growth given up by spending, `future_value - amount`.
+    pub lost_gain: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct GoalProgressRequest {
+    pub target_amount: f64,
+    pub current_amount: f64,
+    pub monthly_contribution: f64,
+    /// Growth of the saved amount, compounded monthly; none by default.
+    pub annual_return: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct GoalProgressResponse {
+    pub percent_complete: f64,
+    pub remaining_amount: f64,
+    /// -1.0 when the goal is never reached.
+    pub months_remaining: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct SubscriptionRequest {
+    pub monthly_cost: f64,
+    pub uses_per_month: f64,
+    /// Price of a single use without the subscription.
+    pub per_use_price: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct SubscriptionResponse {
+    pub cost_per_use: f64,
+    /// Paying per use instead, only with `per_use_price`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pay_per_use_cost: Option<f64>,
+    /// Saved by subscribing each month; negative when paying per use is cheaper.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monthly_savings: Option<f64>,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct WageErosionRequest {
+    pub current_salary: f64,
+    /// Yearly raise and inflation, in percent.
+    pub annual_raise: f64,
+    pub inflation_rate: f64,
+    /// Years, at most 100.
+    pub horizon: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct WageErosionYear {
+    pub year: u32,
+    pub nominal_salary: f64,
+    /// In today's money.
+    pub real_salary: f64,
+}
+
+#[derive(Serialize)]
+pub struct WageErosionResponse {
+    /// From today (year 0) to the end of the horizon.
+    pub years: Vec<WageErosionYear>,
+    pub final_real_salary: f64,
+    /// Change of the real salary over the horizon, in percent.
+    pub real_change: f64,
+    /// "grows", "shrinks" or "flat" in real terms.
+    pub real_trend: String,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct NetPayRequest {
+    pub gross_salary: f64,
+    /// Both in percent of the gross salary.
+    pub income_tax_rate: f64,
+    pub social_contribution_rate: f64,
+    /// Flat amounts withheld on top, e.g. union dues.
+    pub fixed_deductions: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct NetPayResponse {
+    /// What is left of `gross_salary` after the three deductions below.
+    pub net_pay: f64,
+    pub income_tax: f64,
+    pub social_contributions: f64,
+    pub fixed_deductions: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct RequiredSavingsRequest {
+    pub target_amount: f64,
+    pub years: f64,
+    pub annual_return: f64,
+    /// Already saved towards the target, grows alongside the monthly savings.
+    pub current_savings: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct RequiredSavingsResponse {
+    /// 0 when the current savings reach the target on their own.
+    pub monthly_savings: f64,
+    pub total_contributions: f64,
+    pub total_growth: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct DebtVsInvestRequest {
+    pub debt_balance: f64,
+    /// Yearly debt interest and expected investment return, in percent.
+    pub debt_rate: f64,
+    pub investment_return: f64,
+    /// Spare cash available every month, for either choice.
+    pub monthly_amount: f64,
+    /// Years, at most 100.
+    pub horizon: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+/// Where one choice stands at the end of the horizon.
+#[derive(Serialize)]
+pub struct DebtVsInvestOutcome {
+    pub investments: f64,
+    pub remaining_debt: f64,
+    pub net_worth: f64,
+}
+
+#[derive(Serialize)]
+pub struct DebtVsInvestResponse {
+    /// Paying the debt off first, then investing the monthly amount.
+    pub pay_debt: DebtVsInvestOutcome,
+    /// Investing from the start while the debt keeps accruing interest.
+    pub invest: DebtVsInvestOutcome,
+    /// "pay_debt" or "invest".
+    pub recommendation: String,
+    /// Net worth gained by following the recommendation.
+    pub advantage: f64,
+    /// When paying the debt first; -1.0 when not within the horizon.
+    pub months_to_debt_free: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct LumpVsDcaRequest {
+    pub total_amount: f64,
+    /// Months over which dollar-cost averaging spreads the amount, at least 1.
+    pub dca_periods: u32,
+    pub annual_return: f64,
+    /// Years, at most 100; must cover the DCA periods.
+    pub horizon: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct LumpVsDcaResponse {
+    pub lump_sum_value: f64,
+    pub dca_value: f64,
+    /// `lump_sum_value - dca_value`, negative when spreading the amount does better.
+    pub difference: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct InflationRequest {
+    pub amount: f64,
+    /// Yearly inflation, in percent.
+    pub inflation_rate: f64,
+    /// At most 100.
+    pub years: f64,
+    /// "future" (what `amount` will cost in `years`) or "past" (what it was worth `years` ago).
+    pub direction: String,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct InflationResponse {
+    pub adjusted_amount: f64,
+    /// From `amount` to `adjusted_amount`, in percent.
+    pub total_change: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+/// One side of a job comparison, the work fields of `HourlyIncomeRequest`.
+#[derive(Deserialize)]
+pub struct JobOffer {
+    pub monthly_income: f64,
+    pub taxes: f64,
+    pub work_hours: f64,
+    pub commute_time: f64,
+    pub work_expenses: f64,
+}
+
+#[derive(Deserialize)]
+pub struct JobCompareRequest {
+    pub offer_a: JobOffer,
+    pub offer_b: JobOffer,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct JobOfferResult {
+    pub real_hourly_income: f64,
+    pub nominal_hourly_income: f64,
+    pub net_income: f64,
+    pub efficiency: f64,
+}
+
+#[derive(Serialize)]
+pub struct JobCompareResponse {
+    pub offer_a: JobOfferResult,
+    pub offer_b: JobOfferResult,
+    /// "a", "b" or "equal", by real hourly income.
+    pub better_offer: String,
+    /// How much higher the better offer's real hourly income is, in percent of the other's.
+    pub real_rate_difference: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct RecurringCostRequest {
+    /// Price of one occurrence, e.g. a coffee.
+    pub cost: f64,
+    /// "daily", "weekly" or "monthly".
+    pub frequency: String,
+    /// At most 100.
+    pub years: f64,
+    /// Return the money would have earned invested instead, in percent; 0 when absent.
+    pub annual_return: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct RecurringCostResponse {
+    pub annual_cost: f64,
+    /// Spent over all the years.
+    pub total_cost: f64,
+    /// What the same money would have grown into, invested monthly.
+    pub future_value: f64,
+    /// Growth given up by spending, `future_value - total_cost`.
+    pub lost_gain: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct BlendedRateRequest {
+    pub debts: Vec<Debt>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct BlendedRateResponse {
+    pub total_balance: f64,
+    /// Average of the debts' rates weighted by balance, in percent; 0 when nothing is owed.
+    pub blended_rate: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct SavingsAccount {
+    pub name: String,
+    /// Annual percentage yield, interest compounding included.
+    pub apy: f64,
+}
+
+#[derive(Deserialize)]
+pub struct SavingsCompareRequest {
+    pub principal: f64,
+    pub monthly_deposit: f64,
+    /// At most 100.
+    pub years: f64,
+    /// At most 10.
+    pub accounts: Vec<SavingsAccount>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct SavingsAccountResult {
+    pub name: String,
+    pub apy: f64,
+    pub final_balance: f64,
+    pub interest_earned: f64,
+    /// 1 for the highest balance; ties keep the order they were sent in.
+    pub rank: u32,
+}
+
+#[derive(Serialize)]
+pub struct SavingsCompareResponse {
+    pub best_balance: f64,
+    pub best_account: String,
+    pub total_deposited: f64,
+    /// Sorted by rank.
+    pub accounts: Vec<SavingsAccountResult>,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct RoundUpRequest {
+    pub transactions_per_month: f64,
+    /// Average spare change put aside per transaction.
+    pub round_up: f64,
+    /// At most 100.
+    pub years: f64,
+    /// Return on the saved change, in percent; 0 when absent.
+    pub annual_return: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct RoundUpResponse {
+    pub monthly_savings: f64,
+    pub annual_savings: f64,
+    /// Put aside over all the years.
+    pub total_saved: f64,
+    /// The round-ups invested monthly at `annual_return`.
+    pub future_value: f64,
+    /// `future_value - total_saved`.
+    pub investment_gain: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct AffordabilityRequest {
+    pub monthly_income: f64,
+    /// Payments on loans the buyer already has.
+    pub monthly_debt_payments: f64,
+    /// Largest share of income all debt payments may take, in percent, e.g. 36.
+    pub dti_ratio: f64,
+    pub mortgage_rate: f64,
+    /// Years.
+    pub term: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct AffordabilityResponse {
+    pub max_monthly_payment: f64,
+    /// Principal that `max_monthly_payment` pays off over the term.
+    pub max_loan_amount: f64,
+    /// Debt-to-income ratio at the maximum payment, in percent; equal to
+    /// `dti_ratio` unless existing debts alone are above it.
+    pub debt_to_income: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct BillSplitRequest {
+    pub bill_total: f64,
+    pub tip_percent: f64,
+    /// From 1 to 100.
+    pub people: u32,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct BillSplitResponse {
+    /// The largest share; when the total doesn't divide evenly the first
+    /// people pay one cent more than the rest.
+    pub per_person: f64,
+    /// What each person pays, adding up to `grand_total`.
+    pub shares: Vec<f64>,
+    pub total_tip: f64,
+    pub grand_total: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct RaiseImpactRequest {
+    /// Yearly salary before the raise.
+    pub current_salary: f64,
+    /// Exactly one of `raise_amount` (per year) and `raise_percent` must be given.
+    pub raise_amount: Option<f64>,
+    pub raise_percent: Option<f64>,
+    /// Raises expected every year afterwards either way, in percent.
+    pub annual_raise: f64,
+    /// Years until retirement, from 1 to 100.
+    pub years: f64,
+    /// Yearly return when the extra pay is invested; leave out to skip investing.
+    pub invest_return: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct RaiseImpactResponse {
+    /// The raise per year in money, however it was given.
+    pub raise_amount: f64,
+    pub final_salary_with_raise: f64,
+    pub final_salary_without_raise: f64,
+    /// Extra pay earned by retirement thanks to the raise.
+    pub lifetime_difference: f64,
+    /// The extra pay invested each year, at retirement; only with `invest_return`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invested_difference: Option<f64>,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct BudgetRequest {
+    /// Take-home pay per month.
+    pub monthly_income: f64,
+    /// Shares of income in percent, 50/30/20 by default; together they must make 100.
+    pub needs_percent: Option<f64>,
+    pub wants_percent: Option<f64>,
+    pub savings_percent: Option<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct BudgetResponse {
+    pub needs: f64,
+    pub wants: f64,
+    pub savings: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct CostOfWaitingRequest {
+    pub monthly_contribution: f64,
+    pub annual_return: f64,
+    /// Years from now until the money is needed, at most 100.
+    pub years: f64,
+    /// Years before contributions start, at most `years`.
+    pub delay: f64,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct CostOfWaitingResponse {
+    pub start_now_value: f64,
+    pub start_later_value: f64,
+    /// What the delay costs at the end of the horizon.
+    pub cost_of_waiting: f64,
+    pub currency_symbol: String,
+    pub chart: String,
+}
+
+#[derive(Deserialize)]
+pub struct LoanTermsRequest {
+    pub amount: f64,
+    pub rate: f64,
+    /// Loan terms to compare, in years; at most 10.
+    pub terms: Vec<f64>,
+    #[serde(flatten)]
+    pub options: CommonOptions,
+}
+
+#[derive(Serialize)]
+pub struct LoanTermResult {
+    pub term: f64,
+    pub monthly_payment: f64,
+    pub total_interest: f64,
+    pub total_paid: f64,
+}
+
+#[derive(Serialize)]
+pub struct LoanTermsResponse {
+    /// In the order the terms were sent.
+    pub terms: Vec<LoanTermResult>,
+    pub lowest_monthly_payment: f64,
+    pub lowest_total_interest: f64,
     pub currency_symbol: String,
     pub chart: String,
 }
+
+/// One bar or slice of a chart, for clients drawing it themselves.
+#[derive(Serialize)]
+pub struct SeriesPoint {
+    pub label: String,
+    pub value: f64,
+}
+
+#[derive(Serialize)]
+pub struct ConvertedAmount {
+    pub currency: String,
+    pub currency_symbol: String,
+    pub amount: f64,
+}
+
+#[derive(Serialize)]
+pub struct ApiErrorDetail {
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Path that failed, on internal errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+}
+
+/// Error envelope returned by every failing endpoint:
+/// `{ "error": { "code": "BAD_REQUEST", "message": "...", "field": "..." } }`
+#[derive(Serialize)]
+pub struct ApiError {
+    pub error: ApiErrorDetail,
+}
+
+impl ApiError {
+    pub fn new(code: &'static str, message: impl Into<String>, field: Option<String>) -> Self {
+        ApiError { error: ApiErrorDetail { code, message: message.into(), field, endpoint: None } }
+    }
+
+    /// An unexpected failure while serving `path`; the cause stays in the worker logs.
+    pub fn internal(path: &str) -> Self {
+        let mut error = ApiError::new("INTERNAL_ERROR", format!("{} failed unexpectedly", path), None);
+        error.error.endpoint = Some(path.to_string());
+        error
+    }
+
+    /// Builds a 400 error, picking the offending field out of the message when it names one
+    /// (serde quotes it in backticks, calculator checks start with it).
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let field = match message.split('`').nth(1) {
+            Some(field) => Some(field.to_string()),
+            None => message
+                .split_once(" must ")
+                .map(|(field, _)| field)
+                .filter(|field| field.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+                .map(str::to_string),
+        };
+        ApiError::new("BAD_REQUEST", message, field)
+    }
+
+    pub fn not_found(path: &str) -> Self {
+        ApiError::new("NOT_FOUND", format!("No endpoint at {}", path), None)
+    }
+}
+
+#[derive(Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    pub version: &'static str,
+    pub commit: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct CurrencyInfo {
+    pub code: &'static str,
+    pub symbol: &'static str,
+    /// "before" ("$1,234.56") or "after" ("1 234,56 ₴") the amount.
+    pub symbol_position: &'static str,
+    /// Decimal places used unless a request sets `precision`.
+    pub precision: u32,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BatchItem {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub params: serde_json::Value,
+}
+
+impl_request_options!(
+    HourlyIncomeRequest,
+    TimeValueRequest,
+    CreditRequest,
+    InvestmentRequest,
+    RetirementRequest,
+    DebtPayoffRequest,
+    DebtStrategyRequest,
+    EmergencyFundRequest,
+    TaxRequest,
+    BuyRentRequest,
+    SavingsRateRequest,
+    FireRequest,
+    CompoundRequest,
+    FreelanceRateRequest,
+    WageConvertRequest,
+    RefinanceRequest,
+    RetirementMonteCarloRequest,
+    OpportunityCostRequest,
+    GoalProgressRequest,
+    SubscriptionRequest,
+    WageErosionRequest,
+    NetPayRequest,
+    RequiredSavingsRequest,
+    DebtVsInvestRequest,
+    LumpVsDcaRequest,
+    InflationRequest,
+    JobCompareRequest,
+    RecurringCostRequest,
+    BlendedRateRequest,
+    SavingsCompareRequest,
+    RoundUpRequest,
+    AffordabilityRequest,
+    BillSplitRequest,
+    RaiseImpactRequest,
+    BudgetRequest,
+    CostOfWaitingRequest,
+    LoanTermsRequest,
+);
+
+impl_primary_result!(
+    HourlyIncomeResponse => real_hourly_income,
+    TimeValueResponse => time_value,
+    CreditResponse => monthly_payment,
+    InvestmentResponse => future_value,
+    RetirementResponse => future_value,
+    DebtPayoffResponse => total_paid,
+    DebtStrategyResponse => interest_saved,
+    EmergencyFundResponse => target_amount,
+    TaxResponse => net_income,
+    BuyRentResponse => net_buy_position,
+    SavingsRateResponse => annual_savings,
+    FireResponse => fire_target,
+    CompoundResponse => final_amount,
+    FreelanceRateResponse => hourly_rate,
+    WageConvertResponse => annual,
+    RefinanceResponse => lifetime_savings,
+    RetirementMonteCarloResponse => percentile_50,
+    OpportunityCostResponse => lost_gain,
+    GoalProgressResponse => remaining_amount,
+    SubscriptionResponse => cost_per_use,
+    WageErosionResponse => final_real_salary,
+    NetPayResponse => net_pay,
+    RequiredSavingsResponse => monthly_savings,
+    DebtVsInvestResponse => advantage,
+    LumpVsDcaResponse => difference,
+    InflationResponse => adjusted_amount,
+    JobCompareResponse => real_rate_difference as Percent,
+    RecurringCostResponse => annual_cost,
+    BlendedRateResponse => total_balance,
+    SavingsCompareResponse => best_balance,
+    RoundUpResponse => monthly_savings,
+    AffordabilityResponse => max_loan_amount,
+    BillSplitResponse => per_person,
+    RaiseImpactResponse => lifetime_difference,
+    BudgetResponse => needs,
+    CostOfWaitingResponse => cost_of_waiting,
+    LoanTermsResponse => lowest_total_interest,
+);
+
+impl Validate for HourlyIncomeRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "monthly_income", self.monthly_income);
+        check_rate(&mut errors, "taxes", self.taxes);
+        check_non_negative(&mut errors, "work_hours", self.work_hours);
+        check_non_negative(&mut errors, "commute_time", self.commute_time);
+        check_non_negative(&mut errors, "work_expenses", self.work_expenses);
+        errors
+    }
+}
+
+impl Validate for TimeValueRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "annual_income", self.annual_income);
+        check_non_negative(&mut errors, "annual_hours", self.annual_hours);
+        if let Some(factor) = self.ppp_factor
+            && factor <= 0.0
+        {
+            errors.push("ppp_factor must be greater than 0".to_string());
+        }
+        errors
+    }
+}
+
+impl Validate for CreditRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "amount", self.amount);
+        check_rate(&mut errors, "rate", self.rate);
+        // The schedule holds a row per payment, an unbounded term would not fit in memory
+        check_range(&mut errors, "term", self.term, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for InvestmentRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "initial_amount", self.initial_amount);
+        check_non_negative(&mut errors, "monthly_contribution", self.monthly_contribution);
+        check_growth(&mut errors, "annual_return", self.annual_return);
+        if let Some(low) = self.return_low {
+            check_growth(&mut errors, "return_low", low);
+        }
+        if let Some(high) = self.return_high {
+            check_growth(&mut errors, "return_high", high);
+        }
+        if let Some(inflation) = self.inflation_rate {
+            check_growth(&mut errors, "inflation_rate", inflation);
+        }
+        // The growth chart collects every compounding period before sampling them down
+        check_range(&mut errors, "period", self.period, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for RetirementRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "current_age", self.current_age);
+        if self.retirement_age <= self.current_age {
+            errors.push("retirement_age must be greater than current_age".to_string());
+        }
+        check_non_negative(&mut errors, "desired_income", self.desired_income);
+        check_non_negative(&mut errors, "current_savings", self.current_savings);
+        check_non_negative(&mut errors, "monthly_savings", self.monthly_savings);
+        check_growth(&mut errors, "expected_return", self.expected_return);
+        if let Some(inflation) = self.inflation_rate {
+            check_growth(&mut errors, "inflation_rate", inflation);
+        }
+        if let Some(rate) = self.withdrawal_rate {
+            if rate <= 0.0 {
+                errors.push("withdrawal_rate must be greater than 0".to_string());
+            } else {
+                check_rate(&mut errors, "withdrawal_rate", rate);
+            }
+        }
+        errors
+    }
+}
+
+impl Validate for DebtPayoffRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "balance", self.balance);
+        check_rate(&mut errors, "interest_rate", self.interest_rate);
+        check_non_negative(&mut errors, "monthly_payment", self.monthly_payment);
+        check_non_negative(&mut errors, "extra_payment", self.extra_payment);
+        errors
+    }
+}
+
+fn check_debts(errors: &mut Vec<String>, debts: &[Debt]) {
+    for (i, debt) in debts.iter().enumerate() {
+        check_non_negative(errors, &format!("debts[{}].balance", i), debt.balance);
+        check_rate(errors, &format!("debts[{}].rate", i), debt.rate);
+        check_non_negative(errors, &format!("debts[{}].min_payment", i), debt.min_payment);
+    }
+}
+
+impl Validate for DebtStrategyRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_debts(&mut errors, &self.debts);
+        check_non_negative(&mut errors, "extra_payment", self.extra_payment);
+        errors
+    }
+}
+
+impl Validate for EmergencyFundRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "monthly_expenses", self.monthly_expenses);
+        check_non_negative(&mut errors, "months_coverage", self.months_coverage);
+        check_non_negative(&mut errors, "current_savings", self.current_savings);
+        check_non_negative(&mut errors, "monthly_contribution", self.monthly_contribution);
+        errors
+    }
+}
+
+impl Validate for TaxRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "income", self.income);
+        check_rate(&mut errors, "tax_rate", self.tax_rate);
+        let brackets = self.brackets.as_deref().unwrap_or_default();
+        for (i, bracket) in brackets.iter().enumerate() {
+            check_rate(&mut errors, &format!("brackets[{}].rate", i), bracket.rate);
+            // Bands are read in order, each one starting where the previous one ends
+            match (i.checked_sub(1).map(|prev| brackets[prev].up_to), bracket.up_to) {
+                (_, None) if i + 1 < brackets.len() => {
+                    errors.push(format!("brackets[{}].up_to must not be null except in the last bracket", i));
+                },
+                (Some(Some(prev)), Some(up_to)) if up_to <= prev => {
+                    errors.push(format!("brackets[{}].up_to must be greater than brackets[{}].up_to", i, i - 1));
+                },
+                _ => {},
+            }
+        }
+        if let Some(allowance) = self.allowance {
+            check_non_negative(&mut errors, "allowance", allowance);
+        }
+        errors
+    }
+}
+
+impl Validate for BuyRentRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "property_price", self.property_price);
+        check_non_negative(&mut errors, "down_payment", self.down_payment);
+        check_rate(&mut errors, "mortgage_rate", self.mortgage_rate);
+        check_range(&mut errors, "mortgage_term", self.mortgage_term, 0.0, 100.0);
+        check_non_negative(&mut errors, "monthly_rent", self.monthly_rent);
+        check_growth(&mut errors, "rent_growth", self.rent_growth);
+        check_growth(&mut errors, "property_growth", self.property_growth);
+        // Simulated month by month
+        check_range(&mut errors, "horizon", self.horizon, 0.0, 100.0);
+        if let Some(overpayment) = self.monthly_overpayment {
+            check_non_negative(&mut errors, "monthly_overpayment", overpayment);
+        }
+        if let Some(saved) = self.current_down_savings {
+            check_non_negative(&mut errors, "current_down_savings", saved);
+        }
+        if let Some(contribution) = self.monthly_down_contribution {
+            check_non_negative(&mut errors, "monthly_down_contribution", contribution);
+        }
+        errors
+    }
+}
+
+impl Validate for SavingsRateRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "monthly_income", self.monthly_income);
+        check_non_negative(&mut errors, "monthly_expenses", self.monthly_expenses);
+        if let Some(expected_return) = self.expected_return {
+            check_growth(&mut errors, "expected_return", expected_return);
+        }
+        errors
+    }
+}
+
+impl Validate for FireRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "annual_expenses", self.annual_expenses);
+        check_non_negative(&mut errors, "annual_savings", self.annual_savings);
+        check_growth(&mut errors, "expected_return", self.expected_return);
+        if let Some(age) = self.current_age {
+            check_non_negative(&mut errors, "current_age", age);
+        }
+        errors
+    }
+}
+
+impl Validate for CompoundRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "principal", self.principal);
+        check_growth(&mut errors, "annual_rate", self.annual_rate);
+        // One growth point per year is always computed
+        check_range(&mut errors, "years", self.years, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for FreelanceRateRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "desired_net_income", self.desired_net_income);
+        check_non_negative(&mut errors, "billable_hours", self.billable_hours);
+        check_non_negative(&mut errors, "business_expenses", self.business_expenses);
+        check_rate(&mut errors, "tax_rate", self.tax_rate);
+        errors
+    }
+}
+
+impl Validate for WageConvertRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(annual) = self.annual_salary {
+            check_non_negative(&mut errors, "annual_salary", annual);
+        }
+        if let Some(hourly) = self.hourly_rate {
+            check_non_negative(&mut errors, "hourly_rate", hourly);
+        }
+        check_range(&mut errors, "hours_per_week", self.hours_per_week, 0.0, 168.0);
+        check_range(&mut errors, "weeks_per_year", self.weeks_per_year, 0.0, 53.0);
+        errors
+    }
+}
+
+impl Validate for RefinanceRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "current_balance", self.current_balance);
+        check_rate(&mut errors, "current_rate", self.current_rate);
+        // Both loans are amortized month by month, like credit
+        check_range(&mut errors, "remaining_term", self.remaining_term, 0.0, 100.0);
+        check_rate(&mut errors, "new_rate", self.new_rate);
+        check_range(&mut errors, "new_term", self.new_term, 0.0, 100.0);
+        check_non_negative(&mut errors, "closing_costs", self.closing_costs);
+        errors
+    }
+}
+
+impl Validate for RetirementMonteCarloRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        // Every trial simulates each year in between, so both ages stay human
+        check_range(&mut errors, "current_age", self.current_age, 0.0, MAX_AGE);
+        check_range(&mut errors, "retirement_age", self.retirement_age, 0.0, MAX_AGE);
+        if self.retirement_age <= self.current_age {
+            errors.push("retirement_age must be greater than current_age".to_string());
+        }
+        check_non_negative(&mut errors, "desired_income", self.desired_income);
+        check_non_negative(&mut errors, "current_savings", self.current_savings);
+        check_non_negative(&mut errors, "monthly_savings", self.monthly_savings);
+        check_growth(&mut errors, "expected_return", self.expected_return);
+        check_range(&mut errors, "volatility", self.volatility, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for OpportunityCostRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "amount", self.amount);
+        check_growth(&mut errors, "annual_return", self.annual_return);
+        check_range(&mut errors, "years", self.years, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for GoalProgressRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "target_amount", self.target_amount);
+        check_non_negative(&mut errors, "current_amount", self.current_amount);
+        check_non_negative(&mut errors, "monthly_contribution", self.monthly_contribution);
+        if let Some(annual_return) = self.annual_return {
+            check_growth(&mut errors, "annual_return", annual_return);
+        }
+        errors
+    }
+}
+
+impl Validate for SubscriptionRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "monthly_cost", self.monthly_cost);
+        check_non_negative(&mut errors, "uses_per_month", self.uses_per_month);
+        if let Some(price) = self.per_use_price {
+            check_non_negative(&mut errors, "per_use_price", price);
+        }
+        errors
+    }
+}
+
+impl Validate for WageErosionRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "current_salary", self.current_salary);
+        check_growth(&mut errors, "annual_raise", self.annual_raise);
+        check_growth(&mut errors, "inflation_rate", self.inflation_rate);
+        check_range(&mut errors, "horizon", self.horizon, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for NetPayRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "gross_salary", self.gross_salary);
+        check_rate(&mut errors, "income_tax_rate", self.income_tax_rate);
+        check_rate(&mut errors, "social_contribution_rate", self.social_contribution_rate);
+        if let Some(deductions) = self.fixed_deductions {
+            check_non_negative(&mut errors, "fixed_deductions", deductions);
+        }
+        errors
+    }
+}
+
+impl Validate for RequiredSavingsRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "target_amount", self.target_amount);
+        if self.years <= 0.0 {
+            errors.push("years must be greater than 0".to_string());
+        }
+        check_growth(&mut errors, "annual_return", self.annual_return);
+        if let Some(savings) = self.current_savings {
+            check_non_negative(&mut errors, "current_savings", savings);
+        }
+        errors
+    }
+}
+
+impl Validate for DebtVsInvestRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "debt_balance", self.debt_balance);
+        check_rate(&mut errors, "debt_rate", self.debt_rate);
+        check_growth(&mut errors, "investment_return", self.investment_return);
+        check_non_negative(&mut errors, "monthly_amount", self.monthly_amount);
+        check_range(&mut errors, "horizon", self.horizon, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for LumpVsDcaRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "total_amount", self.total_amount);
+        check_growth(&mut errors, "annual_return", self.annual_return);
+        check_range(&mut errors, "horizon", self.horizon, 0.0, 100.0);
+        if self.dca_periods == 0 {
+            errors.push("dca_periods must be at least 1".to_string());
+        } else if self.dca_periods as f64 > (self.horizon * 12.0).round() {
+            errors.push("dca_periods must not exceed the horizon in months".to_string());
+        }
+        errors
+    }
+}
+
+impl Validate for InflationRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "amount", self.amount);
+        check_growth(&mut errors, "inflation_rate", self.inflation_rate);
+        check_range(&mut errors, "years", self.years, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for JobCompareRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (name, offer) in [("offer_a", &self.offer_a), ("offer_b", &self.offer_b)] {
+            check_non_negative(&mut errors, &format!("{}.monthly_income", name), offer.monthly_income);
+            check_rate(&mut errors, &format!("{}.taxes", name), offer.taxes);
+            check_non_negative(&mut errors, &format!("{}.work_hours", name), offer.work_hours);
+            check_non_negative(&mut errors, &format!("{}.commute_time", name), offer.commute_time);
+            check_non_negative(&mut errors, &format!("{}.work_expenses", name), offer.work_expenses);
+        }
+        errors
+    }
+}
+
+impl Validate for RecurringCostRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "cost", self.cost);
+        check_range(&mut errors, "years", self.years, 0.0, 100.0);
+        if let Some(annual_return) = self.annual_return {
+            check_growth(&mut errors, "annual_return", annual_return);
+        }
+        errors
+    }
+}
+
+impl Validate for BlendedRateRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.debts.is_empty() {
+            errors.push("debts must not be empty".to_string());
+        }
+        check_debts(&mut errors, &self.debts);
+        errors
+    }
+}
+
+impl Validate for SavingsCompareRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "principal", self.principal);
+        check_non_negative(&mut errors, "monthly_deposit", self.monthly_deposit);
+        check_range(&mut errors, "years", self.years, 0.0, 100.0);
+        if self.accounts.is_empty() {
+            errors.push("accounts must not be empty".to_string());
+        } else if self.accounts.len() > MAX_SAVINGS_ACCOUNTS {
+            errors.push(format!("accounts must have at most {} entries", MAX_SAVINGS_ACCOUNTS));
+        }
+        for (i, account) in self.accounts.iter().enumerate() {
+            check_rate(&mut errors, &format!("accounts[{}].apy", i), account.apy);
+        }
+        errors
+    }
+}
+
+impl Validate for RoundUpRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "transactions_per_month", self.transactions_per_month);
+        check_non_negative(&mut errors, "round_up", self.round_up);
+        check_range(&mut errors, "years", self.years, 0.0, 100.0);
+        if let Some(annual_return) = self.annual_return {
+            check_growth(&mut errors, "annual_return", annual_return);
+        }
+        errors
+    }
+}
+
+impl Validate for AffordabilityRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "monthly_income", self.monthly_income);
+        check_non_negative(&mut errors, "monthly_debt_payments", self.monthly_debt_payments);
+        check_rate(&mut errors, "dti_ratio", self.dti_ratio);
+        check_rate(&mut errors, "mortgage_rate", self.mortgage_rate);
+        check_range(&mut errors, "term", self.term, 0.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for BillSplitRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "bill_total", self.bill_total);
+        check_rate(&mut errors, "tip_percent", self.tip_percent);
+        // One share per person is returned, so the group stays table-sized
+        check_range(&mut errors, "people", self.people as f64, 1.0, 100.0);
+        errors
+    }
+}
+
+impl Validate for RaiseImpactRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "current_salary", self.current_salary);
+        if let Some(amount) = self.raise_amount {
+            check_non_negative(&mut errors, "raise_amount", amount);
+        }
+        if let Some(percent) = self.raise_percent {
+            check_non_negative(&mut errors, "raise_percent", percent);
+        }
+        check_growth(&mut errors, "annual_raise", self.annual_raise);
+        check_range(&mut errors, "years", self.years, 1.0, 100.0);
+        if let Some(rate) = self.invest_return {
+            check_growth(&mut errors, "invest_return", rate);
+        }
+        errors
+    }
+}
+
+impl Validate for BudgetRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "monthly_income", self.monthly_income);
+        for (field, percent) in [
+            ("needs_percent", self.needs_percent),
+            ("wants_percent", self.wants_percent),
+            ("savings_percent", self.savings_percent),
+        ] {
+            if let Some(percent) = percent {
+                check_rate(&mut errors, field, percent);
+            }
+        }
+        errors
+    }
+}
+
+impl Validate for CostOfWaitingRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "monthly_contribution", self.monthly_contribution);
+        check_growth(&mut errors, "annual_return", self.annual_return);
+        check_range(&mut errors, "years", self.years, 0.0, 100.0);
+        check_range(&mut errors, "delay", self.delay, 0.0, self.years.max(0.0));
+        errors
+    }
+}
+
+impl Validate for LoanTermsRequest {
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        check_non_negative(&mut errors, "amount", self.amount);
+        check_rate(&mut errors, "rate", self.rate);
+        if self.terms.is_empty() {
+            errors.push("terms must not be empty".to_string());
+        } else if self.terms.len() > MAX_LOAN_TERMS {
+            errors.push(format!("terms must have at most {} entries", MAX_LOAN_TERMS));
+        }
+        for (i, &term) in self.terms.iter().enumerate() {
+            check_range(&mut errors, &format!("terms[{}]", i), term, 0.0, 100.0);
+        }
+        errors
+    }
+}
@@ -0,0 +1,40 @@
+//! PNG versions of the SVG charts, for clients that can't embed SVG.
+//!
+//! Behind the `png` feature (on by default). The rasterizer and the embedded
+//! font (740 KB) count against the worker's bundle size limit, 3 MB compressed
+//! on the free plan and 10 MB on paid plans; build with
+//! `--no-default-features` to leave them out. Rasterizing also takes CPU time
+//! the free plan's 10 ms per request may not cover for larger charts.
+
+/// Charts are drawn at twice their SVG size to stay sharp on high-density screens.
+#[cfg(feature = "png")]
+const SCALE: f32 = 2.0;
+
+/// Renders `svg` and returns it as a `data:image/png;base64,...` URL.
+#[cfg(feature = "png")]
+pub fn png_data_url(svg: &str) -> Result<String, String> {
+    use base64::Engine;
+    use resvg::{tiny_skia, usvg};
+    use std::sync::Arc;
+
+    // Workers have no system fonts; the charts only ask for the generic sans-serif
+    static FONT: &[u8] = include_bytes!("../fonts/DejaVuSans.ttf");
+    let mut options = usvg::Options::default();
+    let fontdb = Arc::make_mut(&mut options.fontdb);
+    fontdb.load_font_data(FONT.to_vec());
+    fontdb.set_sans_serif_family("DejaVu Sans");
+
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|e| e.to_string())?;
+    let size = tree.size();
+    let mut pixmap = tiny_skia::Pixmap::new((size.width() * SCALE).ceil() as u32, (size.height() * SCALE).ceil() as u32)
+        .ok_or("Chart too large to render as PNG")?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(SCALE, SCALE), &mut pixmap.as_mut());
+
+    let png = pixmap.encode_png().map_err(|e| e.to_string())?;
+    Ok(format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png)))
+}
+
+#[cfg(not(feature = "png"))]
+pub fn png_data_url(_svg: &str) -> Result<String, String> {
+    Err("chart_format png is not available, the worker was built without the png feature".to_string())
+}
@@ -0,0 +1,810 @@
+//! Machine-readable description of every calculator, served at `GET /schema`.
+//! Maintained by hand: keep it in step with the request and response structs in `models`.
+
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+pub struct Field {
+    pub name: &'static str,
+    /// JSON type: "number", "integer", "string", "boolean", "array" or "object".
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub required: bool,
+}
+
+const fn field(name: &'static str, kind: &'static str) -> Field {
+    Field { name, kind, required: true }
+}
+
+/// A request field that may be left out, or a response field that may be absent.
+const fn optional(name: &'static str, kind: &'static str) -> Field {
+    Field { name, kind, required: false }
+}
+
+struct CalculatorSchema {
+    kind: &'static str,
+    request: &'static [Field],
+    response: &'static [Field],
+    /// Also returned preformatted as `formatted_<primary_field>`.
+    primary_field: &'static str,
+    /// "money" (formatted in the currency, converted to `display_currency`) or "percent".
+    primary_kind: &'static str,
+}
+
+/// Options accepted by every calculator, see `CommonOptions`.
+const COMMON_REQUEST: &[Field] = &[
+    field("currency", "string"),
+    optional("strict_currency", "boolean"),
+    optional("rates", "object"),
+    optional("display_currency", "string"),
+    optional("precision", "integer"),
+    optional("lang", "string"),
+    optional("include_chart", "boolean"),
+    optional("theme", "string"),
+    optional("palette", "string"),
+    optional("chart_format", "string"),
+    optional("chart_width", "integer"),
+    optional("chart_height", "integer"),
+    optional("include_series", "boolean"),
+];
+
+const CALCULATORS: &[CalculatorSchema] = &[
+    CalculatorSchema {
+        kind: "hourly-income",
+        request: &[
+            field("monthly_income", "number"),
+            field("taxes", "number"),
+            field("work_hours", "number"),
+            field("commute_time", "number"),
+            field("work_expenses", "number"),
+        ],
+        response: &[
+            field("real_hourly_income", "number"),
+            field("nominal_hourly_income", "number"),
+            field("net_income", "number"),
+            field("efficiency", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "real_hourly_income",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "time-value",
+        request: &[
+            field("annual_income", "number"),
+            field("annual_hours", "number"),
+            optional("ppp_factor", "number"),
+        ],
+        response: &[
+            field("time_value", "number"),
+            field("ppp_adjusted_value", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "time_value",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "investment",
+        request: &[
+            field("initial_amount", "number"),
+            field("monthly_contribution", "number"),
+            field("annual_return", "number"),
+            optional("return_low", "number"),
+            optional("return_high", "number"),
+            optional("inflation_rate", "number"),
+            field("period", "number"),
+            optional("compounding", "string"),
+            optional("annuity_due", "boolean"),
+            optional("include_growth_chart", "boolean"),
+            optional("chart_type", "string"),
+        ],
+        response: &[
+            field("future_value", "number"),
+            field("total_contributions", "number"),
+            field("total_gain", "number"),
+            field("roi", "number"),
+            field("real_future_value", "number"),
+            field("real_roi", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+            optional("growth_chart", "string"),
+            optional("scenarios", "object"),
+            optional("scenario_chart", "string"),
+        ],
+        primary_field: "future_value",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "credit",
+        request: &[
+            field("amount", "number"),
+            field("rate", "number"),
+            field("term", "number"),
+            optional("payment_frequency", "string"),
+            optional("include_schedule", "boolean"),
+            optional("chart_type", "string"),
+        ],
+        response: &[
+            field("monthly_payment", "number"),
+            optional("biweekly_payment", "number"),
+            field("total_payment", "number"),
+            field("overpayment", "number"),
+            field("effective_annual_rate", "number"),
+            field("first_month_interest", "number"),
+            field("first_month_principal", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+            optional("schedule", "array"),
+        ],
+        primary_field: "monthly_payment",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "retirement",
+        request: &[
+            field("current_age", "number"),
+            field("retirement_age", "number"),
+            field("desired_income", "number"),
+            field("current_savings", "number"),
+            field("monthly_savings", "number"),
+            field("expected_return", "number"),
+            optional("inflation_rate", "number"),
+            optional("withdrawal_rate", "number"),
+            optional("annuity_due", "boolean"),
+        ],
+        response: &[
+            field("future_value", "number"),
+            field("future_value_savings", "number"),
+            field("future_value_contributions", "number"),
+            field("required_capital", "number"),
+            field("gap", "number"),
+            field("future_value_real", "number"),
+            field("required_capital_real", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "future_value",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "debt-payoff",
+        request: &[
+            field("balance", "number"),
+            field("interest_rate", "number"),
+            field("monthly_payment", "number"),
+            field("extra_payment", "number"),
+            optional("chart_type", "string"),
+        ],
+        response: &[
+            field("months", "integer"),
+            field("total_paid", "number"),
+            field("total_interest", "number"),
+            field("payable", "boolean"),
+            optional("minimum_payment_required", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+            optional("timeline_chart", "string"),
+        ],
+        primary_field: "total_paid",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "debt-strategy",
+        request: &[
+            field("debts", "array"),
+            field("extra_payment", "number"),
+        ],
+        response: &[
+            field("snowball", "object"),
+            field("avalanche", "object"),
+            field("recommendation", "string"),
+            field("interest_saved", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "interest_saved",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "emergency-fund",
+        request: &[
+            field("monthly_expenses", "number"),
+            field("months_coverage", "number"),
+            field("current_savings", "number"),
+            field("monthly_contribution", "number"),
+        ],
+        response: &[
+            field("target_amount", "number"),
+            field("remaining_amount", "number"),
+            field("months_to_target", "number"),
+            optional("current_coverage_months", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "target_amount",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "tax",
+        request: &[
+            field("income", "number"),
+            field("tax_rate", "number"),
+            optional("brackets", "array"),
+            optional("allowance", "number"),
+            optional("chart_type", "string"),
+        ],
+        response: &[
+            field("tax_amount", "number"),
+            field("net_income", "number"),
+            field("effective_rate", "number"),
+            field("marginal_rate", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "net_income",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "buy-rent",
+        request: &[
+            field("property_price", "number"),
+            field("down_payment", "number"),
+            field("mortgage_rate", "number"),
+            field("mortgage_term", "number"),
+            field("monthly_rent", "number"),
+            field("rent_growth", "number"),
+            field("property_growth", "number"),
+            field("horizon", "number"),
+            optional("monthly_overpayment", "number"),
+            optional("current_down_savings", "number"),
+            optional("monthly_down_contribution", "number"),
+        ],
+        response: &[
+            field("net_buy_position", "number"),
+            field("net_rent_position", "number"),
+            field("recommendation", "string"),
+            field("total_interest_paid", "number"),
+            optional("break_even_year", "number"),
+            optional("months_to_down_payment", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "net_buy_position",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "savings-rate",
+        request: &[
+            field("monthly_income", "number"),
+            field("monthly_expenses", "number"),
+            optional("expected_return", "number"),
+        ],
+        response: &[
+            field("savings_rate", "number"),
+            field("annual_savings", "number"),
+            field("fi_target", "number"),
+            field("years_to_independence", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "annual_savings",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "fire",
+        request: &[
+            field("current_net_worth", "number"),
+            field("annual_expenses", "number"),
+            field("annual_savings", "number"),
+            field("expected_return", "number"),
+            optional("current_age", "number"),
+        ],
+        response: &[
+            field("fire_target", "number"),
+            field("years_to_fire", "number"),
+            optional("fire_age", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "fire_target",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "compound",
+        request: &[
+            field("principal", "number"),
+            field("annual_rate", "number"),
+            field("years", "number"),
+            optional("compounding", "string"),
+        ],
+        response: &[
+            field("final_amount", "number"),
+            field("interest_earned", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "final_amount",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "freelance-rate",
+        request: &[
+            field("desired_net_income", "number"),
+            field("billable_hours", "number"),
+            field("business_expenses", "number"),
+            field("tax_rate", "number"),
+        ],
+        response: &[
+            field("hourly_rate", "number"),
+            field("gross_income", "number"),
+            field("tax_amount", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "hourly_rate",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "wage-convert",
+        request: &[
+            optional("annual_salary", "number"),
+            optional("hourly_rate", "number"),
+            field("hours_per_week", "number"),
+            field("weeks_per_year", "number"),
+        ],
+        response: &[
+            field("annual", "number"),
+            field("monthly", "number"),
+            field("weekly", "number"),
+            field("daily", "number"),
+            field("hourly", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "annual",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "refinance",
+        request: &[
+            field("current_balance", "number"),
+            field("current_rate", "number"),
+            field("remaining_term", "number"),
+            field("new_rate", "number"),
+            field("new_term", "number"),
+            field("closing_costs", "number"),
+        ],
+        response: &[
+            field("current_monthly_payment", "number"),
+            field("new_monthly_payment", "number"),
+            field("current_total_interest", "number"),
+            field("new_total_interest", "number"),
+            field("lifetime_savings", "number"),
+            optional("break_even_month", "integer"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "lifetime_savings",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "retirement-monte-carlo",
+        request: &[
+            field("current_age", "number"),
+            field("retirement_age", "number"),
+            field("desired_income", "number"),
+            field("current_savings", "number"),
+            field("monthly_savings", "number"),
+            field("expected_return", "number"),
+            field("volatility", "number"),
+            optional("trials", "integer"),
+            optional("seed", "integer"),
+        ],
+        response: &[
+            field("success_probability", "number"),
+            field("required_capital", "number"),
+            field("percentile_10", "number"),
+            field("percentile_50", "number"),
+            field("percentile_90", "number"),
+            field("trials", "integer"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "percentile_50",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "opportunity-cost",
+        request: &[
+            field("amount", "number"),
+            field("annual_return", "number"),
+            field("years", "number"),
+        ],
+        response: &[
+            field("future_value", "number"),
+            field("lost_gain", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "lost_gain",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "goal-progress",
+        request: &[
+            field("target_amount", "number"),
+            field("current_amount", "number"),
+            field("monthly_contribution", "number"),
+            optional("annual_return", "number"),
+        ],
+        response: &[
+            field("percent_complete", "number"),
+            field("remaining_amount", "number"),
+            field("months_remaining", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "remaining_amount",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "subscription",
+        request: &[
+            field("monthly_cost", "number"),
+            field("uses_per_month", "number"),
+            optional("per_use_price", "number"),
+        ],
+        response: &[
+            field("cost_per_use", "number"),
+            optional("pay_per_use_cost", "number"),
+            optional("monthly_savings", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "cost_per_use",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "wage-erosion",
+        request: &[
+            field("current_salary", "number"),
+            field("annual_raise", "number"),
+            field("inflation_rate", "number"),
+            field("horizon", "number"),
+        ],
+        response: &[
+            field("years", "array"),
+            field("final_real_salary", "number"),
+            field("real_change", "number"),
+            field("real_trend", "string"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "final_real_salary",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "net-pay",
+        request: &[
+            field("gross_salary", "number"),
+            field("income_tax_rate", "number"),
+            field("social_contribution_rate", "number"),
+            optional("fixed_deductions", "number"),
+        ],
+        response: &[
+            field("net_pay", "number"),
+            field("income_tax", "number"),
+            field("social_contributions", "number"),
+            field("fixed_deductions", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "net_pay",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "required-savings",
+        request: &[
+            field("target_amount", "number"),
+            field("years", "number"),
+            field("annual_return", "number"),
+            optional("current_savings", "number"),
+        ],
+        response: &[
+            field("monthly_savings", "number"),
+            field("total_contributions", "number"),
+            field("total_growth", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "monthly_savings",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "debt-vs-invest",
+        request: &[
+            field("debt_balance", "number"),
+            field("debt_rate", "number"),
+            field("investment_return", "number"),
+            field("monthly_amount", "number"),
+            field("horizon", "number"),
+        ],
+        response: &[
+            field("pay_debt", "object"),
+            field("invest", "object"),
+            field("recommendation", "string"),
+            field("advantage", "number"),
+            field("months_to_debt_free", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "advantage",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "lump-vs-dca",
+        request: &[
+            field("total_amount", "number"),
+            field("dca_periods", "integer"),
+            field("annual_return", "number"),
+            field("horizon", "number"),
+        ],
+        response: &[
+            field("lump_sum_value", "number"),
+            field("dca_value", "number"),
+            field("difference", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "difference",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "inflation",
+        request: &[
+            field("amount", "number"),
+            field("inflation_rate", "number"),
+            field("years", "number"),
+            field("direction", "string"),
+        ],
+        response: &[
+            field("adjusted_amount", "number"),
+            field("total_change", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "adjusted_amount",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "job-compare",
+        request: &[
+            field("offer_a", "object"),
+            field("offer_b", "object"),
+        ],
+        response: &[
+            field("offer_a", "object"),
+            field("offer_b", "object"),
+            field("better_offer", "string"),
+            field("real_rate_difference", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "real_rate_difference",
+        primary_kind: "percent",
+    },
+    CalculatorSchema {
+        kind: "recurring-cost",
+        request: &[
+            field("cost", "number"),
+            field("frequency", "string"),
+            field("years", "number"),
+            optional("annual_return", "number"),
+        ],
+        response: &[
+            field("annual_cost", "number"),
+            field("total_cost", "number"),
+            field("future_value", "number"),
+            field("lost_gain", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "annual_cost",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "blended-rate",
+        request: &[
+            field("debts", "array"),
+        ],
+        response: &[
+            field("total_balance", "number"),
+            field("blended_rate", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "total_balance",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "savings-compare",
+        request: &[
+            field("principal", "number"),
+            field("monthly_deposit", "number"),
+            field("years", "number"),
+            field("accounts", "array"),
+        ],
+        response: &[
+            field("best_balance", "number"),
+            field("best_account", "string"),
+            field("total_deposited", "number"),
+            field("accounts", "array"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "best_balance",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "round-up",
+        request: &[
+            field("transactions_per_month", "number"),
+            field("round_up", "number"),
+            field("years", "number"),
+            optional("annual_return", "number"),
+        ],
+        response: &[
+            field("monthly_savings", "number"),
+            field("annual_savings", "number"),
+            field("total_saved", "number"),
+            field("future_value", "number"),
+            field("investment_gain", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "monthly_savings",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "affordability",
+        request: &[
+            field("monthly_income", "number"),
+            field("monthly_debt_payments", "number"),
+            field("dti_ratio", "number"),
+            field("mortgage_rate", "number"),
+            field("term", "number"),
+        ],
+        response: &[
+            field("max_monthly_payment", "number"),
+            field("max_loan_amount", "number"),
+            field("debt_to_income", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "max_loan_amount",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "bill-split",
+        request: &[
+            field("bill_total", "number"),
+            field("tip_percent", "number"),
+            field("people", "number"),
+        ],
+        response: &[
+            field("per_person", "number"),
+            field("shares", "array"),
+            field("total_tip", "number"),
+            field("grand_total", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "per_person",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "raise-impact",
+        request: &[
+            field("current_salary", "number"),
+            optional("raise_amount", "number"),
+            optional("raise_percent", "number"),
+            field("annual_raise", "number"),
+            field("years", "number"),
+            optional("invest_return", "number"),
+        ],
+        response: &[
+            field("raise_amount", "number"),
+            field("final_salary_with_raise", "number"),
+            field("final_salary_without_raise", "number"),
+            field("lifetime_difference", "number"),
+            optional("invested_difference", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "lifetime_difference",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "budget",
+        request: &[
+            field("monthly_income", "number"),
+            optional("needs_percent", "number"),
+            optional("wants_percent", "number"),
+            optional("savings_percent", "number"),
+        ],
+        response: &[
+            field("needs", "number"),
+            field("wants", "number"),
+            field("savings", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "needs",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "cost-of-waiting",
+        request: &[
+            field("monthly_contribution", "number"),
+            field("annual_return", "number"),
+            field("years", "number"),
+            field("delay", "number"),
+        ],
+        response: &[
+            field("start_now_value", "number"),
+            field("start_later_value", "number"),
+            field("cost_of_waiting", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "cost_of_waiting",
+        primary_kind: "money",
+    },
+    CalculatorSchema {
+        kind: "loan-terms",
+        request: &[
+            field("amount", "number"),
+            field("rate", "number"),
+            field("terms", "array"),
+        ],
+        response: &[
+            field("terms", "array"),
+            field("lowest_monthly_payment", "number"),
+            field("lowest_total_interest", "number"),
+            field("currency_symbol", "string"),
+            field("chart", "string"),
+        ],
+        primary_field: "lowest_total_interest",
+        primary_kind: "money",
+    },
+];
+
+/// JSON type of the request field `name` of calculator `kind`, common options included.
+pub fn request_field_kind(kind: &str, name: &str) -> Option<&'static str> {
+    let calculator = CALCULATORS.iter().find(|c| c.kind == kind)?;
+    calculator.request.iter().chain(COMMON_REQUEST).find(|f| f.name == name).map(|f| f.kind)
+}
+
+/// `{ "calculators": { "<kind>": { "request": [...], "response": [...] } } }`,
+/// with the common options and the formatted/converted/overflow/series extras filled in.
+pub fn schema() -> serde_json::Value {
+    let calculators: serde_json::Map<String, serde_json::Value> = CALCULATORS
+        .iter()
+        .map(|calculator| {
+            let request: Vec<&Field> = calculator.request.iter().chain(COMMON_REQUEST).collect();
+            let formatted = format!("formatted_{}", calculator.primary_field);
+            let mut response = json!(calculator.response);
+            if let Some(fields) = response.as_array_mut() {
+                fields.push(json!({ "name": formatted, "type": "string", "required": true, "format": calculator.primary_kind }));
+                fields.push(json!(optional("display", "object")));
+                fields.push(json!(optional("overflow", "boolean")));
+                fields.push(json!(optional("series", "array")));
+            }
+            (calculator.kind.to_string(), json!({ "request": request, "response": response }))
+        })
+        .collect();
+    json!({ "calculators": calculators })
+}
@@ -1,6 +1,8 @@
 #!/bin/bash
 
 BASE_URL="http://localhost:8787"
+# With the RATE_LIMIT namespace bound, run with a higher limit, e.g.
+#   wrangler dev --var RATE_LIMIT_PER_MINUTE:1000
 
 test_endpoint() {
     local name=$1
@@ -21,6 +23,45 @@ test_endpoint() {
     echo "-----------------------------------"
 }
 
+test_status() {
+    local name=$1
+    local endpoint=$2
+    local data=$3
+    local expected=$4
+
+    echo "Testing $name..."
+    status=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL$endpoint" \
+        -H "Content-Type: application/json" \
+        -d "$data")
+
+    if [ "$status" = "$expected" ]; then
+        echo "✅ $name: $status"
+    else
+        echo "❌ $name: Expected $expected, got $status"
+    fi
+    echo "-----------------------------------"
+}
+
+test_contains() {
+    local name=$1
+    local endpoint=$2
+    local data=$3
+    local needle=$4
+
+    echo "Testing $name..."
+    response=$(curl -s -X POST "$BASE_URL$endpoint" \
+        -H "Content-Type: application/json" \
+        -d "$data")
+
+    if echo "$response" | grep -q "$needle"; then
+        echo "✅ $name: Found \"$needle\""
+    else
+        echo "❌ $name: Missing \"$needle\""
+        echo "Response: $response"
+    fi
+    echo "-----------------------------------"
+}
+
 # 1. Hourly Income
 test_endpoint "Hourly Income" "/calculate/hourly-income" \
     '{"monthly_income": 3000, "taxes": 20, "work_hours": 160, "commute_time": 40, "work_expenses": 200, "currency": "EUR"}'
@@ -56,3 +97,1104 @@ test_endpoint "Tax" "/calculate/tax" \
 # 9. Buy vs Rent
 test_endpoint "Buy vs Rent" "/calculate/buy-rent" \
     '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": 1000, "rent_growth": 3, "property_growth": 2, "horizon": 10, "currency": "EUR"}'
+
+# 10. Tax (progressive brackets): 10% of 20000, 20% of the next 30000, 30% of the rest
+test_contains "Tax (three bands)" "/calculate/tax" \
+    '{"income": 60000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": 50000, "rate": 20}, {"up_to": null, "rate": 30}], "currency": "UAH"}' \
+    '"tax_amount":11000.0'
+test_contains "Tax (three bands, effective rate)" "/calculate/tax" \
+    '{"income": 60000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": 50000, "rate": 20}, {"up_to": null, "rate": 30}], "currency": "UAH"}' \
+    '"effective_rate":18.3'
+test_contains "Tax (two bands)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": null, "rate": 20}], "currency": "UAH"}' \
+    '"tax_amount":8000.0'
+test_contains "Tax (two bands, effective rate)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": null, "rate": 20}], "currency": "UAH"}' \
+    '"effective_rate":16.0'
+test_contains "Tax (zero income)" "/calculate/tax" \
+    '{"income": 0, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": null, "rate": 20}], "currency": "UAH"}' \
+    '"tax_amount":0.0'
+test_contains "Tax (zero income, effective rate)" "/calculate/tax" \
+    '{"income": 0, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": null, "rate": 20}], "currency": "UAH"}' \
+    '"effective_rate":0.0'
+
+# 11. Credit with amortization schedule
+test_endpoint "Credit (schedule)" "/calculate/credit" \
+    '{"amount": 5000, "rate": 5, "term": 3, "include_schedule": true, "currency": "USD"}'
+
+# 12. Hourly Income with zero work hours must be rejected
+test_status "Hourly Income (zero hours)" "/calculate/hourly-income" \
+    '{"monthly_income": 3000, "taxes": 20, "work_hours": 0, "commute_time": 40, "work_expenses": 200, "currency": "EUR"}' 400
+
+# 13. Tax via query string
+echo "Testing Tax (GET)..."
+response=$(curl -s "$BASE_URL/calculate/tax?income=50000&tax_rate=18&currency=UAH")
+if echo "$response" | grep -q "chart"; then
+    echo "✅ Tax (GET): Success"
+else
+    echo "❌ Tax (GET): Failed"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 14. English chart labels
+test_contains "Hourly Income (en)" "/calculate/hourly-income" \
+    '{"monthly_income": 3000, "taxes": 20, "work_hours": 160, "commute_time": 40, "work_expenses": 200, "currency": "EUR", "lang": "en"}' \
+    "Hourly rate comparison"
+
+# 15. Investment growth line chart
+test_contains "Investment (growth chart)" "/calculate/investment" \
+    '{"initial_amount": 1000, "monthly_contribution": 200, "annual_return": 8, "period": 10, "include_growth_chart": true, "currency": "EUR"}' \
+    "growth_chart"
+
+# 16. Strict currency validation
+test_status "Tax (strict USD)" "/calculate/tax" \
+    '{"income": 5000, "tax_rate": 18, "currency": "USD", "strict_currency": true}' 200
+test_status "Tax (strict lowercase usd)" "/calculate/tax" \
+    '{"income": 5000, "tax_rate": 18, "currency": "usd", "strict_currency": true}' 200
+test_status "Tax (strict bogus currency)" "/calculate/tax" \
+    '{"income": 5000, "tax_rate": 18, "currency": "USDD", "strict_currency": true}' 400
+
+# 17. Investment with annual compounding
+test_endpoint "Investment (annual compounding)" "/calculate/investment" \
+    '{"initial_amount": 1000, "monthly_contribution": 200, "annual_return": 8, "period": 10, "compounding": "annual", "currency": "EUR"}'
+
+# 18. Batch of calculators, unknown type reported per item
+test_contains "Batch" "/calculate/batch" \
+    '[{"type": "tax", "params": {"income": 5000, "tax_rate": 18, "currency": "UAH"}}, {"type": "time-value", "params": {"annual_income": 36000, "annual_hours": 2000, "currency": "USD"}}, {"type": "lottery", "params": {}}]' \
+    "Unknown calculator type: lottery"
+
+# 19. Retirement with inflation
+test_contains "Retirement (inflation)" "/calculate/retirement" \
+    '{"current_age": 30, "retirement_age": 65, "current_savings": 5000, "monthly_savings": 500, "expected_return": 7, "desired_income": 2000, "inflation_rate": 3, "currency": "EUR"}' \
+    "required_capital_real"
+
+# 20. Health reports version
+echo "Testing Health..."
+response=$(curl -s "$BASE_URL/health")
+if echo "$response" | grep -q "version"; then
+    echo "✅ Health: $response"
+else
+    echo "❌ Health: Failed"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 21. 30-year mortgage totals are cent-exact
+test_contains "Credit (30-year totals)" "/calculate/credit" \
+    '{"amount": 300000, "rate": 6.5, "term": 30, "currency": "USD"}' \
+    '"total_payment":682636.71'
+
+# 22. Debt strategy comparison
+test_contains "Debt Strategy" "/calculate/debt-strategy" \
+    '{"debts": [{"balance": 2000, "rate": 5, "min_payment": 50}, {"balance": 8000, "rate": 22, "min_payment": 200}, {"balance": 500, "rate": 15, "min_payment": 25}], "extra_payment": 200, "currency": "USD"}' \
+    '"recommendation":"avalanche"'
+
+# 23. Debt payoff at the exact break-even payment is unpayable
+test_contains "Debt Payoff (break-even)" "/calculate/debt-payoff" \
+    '{"balance": 10000, "interest_rate": 12, "monthly_payment": 100, "extra_payment": 0, "currency": "USD"}' \
+    '"minimum_payment_required":100.01'
+
+# 24. Savings rate
+test_endpoint "Savings Rate" "/calculate/savings-rate" \
+    '{"monthly_income": 4000, "monthly_expenses": 2000, "currency": "USD"}'
+
+# 25. Tax as a donut chart
+test_contains "Tax (pie)" "/calculate/tax" \
+    '{"income": 5000, "tax_rate": 18, "chart_type": "pie", "currency": "UAH"}' \
+    "<circle"
+
+# 26. Tax result echoed in a display currency
+test_contains "Tax (display currency)" "/calculate/tax" \
+    '{"income": 1000, "tax_rate": 0, "currency": "UAH", "display_currency": "EUR", "rates": {"EUR": 1.0, "UAH": 45.0}}' \
+    '"amount":22.22'
+
+# 27. Errors use the JSON envelope
+test_contains "Hourly Income (error envelope)" "/calculate/hourly-income" \
+    '{"monthly_income": 3000, "taxes": 20, "work_hours": 0, "commute_time": 40, "work_expenses": 200, "currency": "EUR"}' \
+    '"field":"work_hours"'
+
+# 28. Credit effective annual rate (12% nominal -> 12.68% APY)
+test_contains "Credit (APY)" "/calculate/credit" \
+    '{"amount": 5000, "rate": 12, "term": 3, "currency": "USD"}' \
+    '"effective_annual_rate":12.68'
+
+# 29. Buy vs Rent with mortgage overpayment
+test_endpoint "Buy vs Rent (overpayment)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": 1000, "rent_growth": 3, "property_growth": 2, "horizon": 10, "monthly_overpayment": 500, "currency": "EUR"}'
+
+# 30. BTC keeps satoshi precision
+test_contains "Tax (BTC precision)" "/calculate/tax" \
+    '{"income": 0.12345678, "tax_rate": 18, "currency": "BTC"}' \
+    '"tax_amount":0.0222222'
+
+# 31. Buy vs Rent break-even year (year 7, and never within the horizon)
+test_contains "Buy vs Rent (break-even)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": 1000, "rent_growth": 3, "property_growth": 3, "horizon": 15, "currency": "EUR"}' \
+    '"break_even_year":7.0'
+test_contains "Buy vs Rent (no break-even)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": 500, "rent_growth": 3, "property_growth": 0, "horizon": 15, "currency": "EUR"}' \
+    '"break_even_year":null'
+
+# 32. Domain validation rejects out-of-range inputs
+test_status "Hourly Income (taxes above 100)" "/calculate/hourly-income" \
+    '{"monthly_income": 3000, "taxes": 101, "work_hours": 160, "commute_time": 40, "work_expenses": 200, "currency": "EUR"}' 400
+test_status "Time Value (negative income)" "/calculate/time-value" \
+    '{"annual_income": -1, "annual_hours": 2000, "currency": "EUR"}' 400
+test_status "Credit (negative amount)" "/calculate/credit" \
+    '{"amount": -5000, "rate": 12, "term": 3, "currency": "USD"}' 400
+test_status "Investment (return below -100)" "/calculate/investment" \
+    '{"initial_amount": 10000, "monthly_contribution": 500, "annual_return": -101, "period": 10, "currency": "USD"}' 400
+test_status "Retirement (retirement age before current age)" "/calculate/retirement" \
+    '{"current_age": 40, "retirement_age": 40, "desired_income": 1000, "current_savings": 0, "monthly_savings": 100, "expected_return": 5, "currency": "EUR"}' 400
+test_status "Debt Payoff (negative balance)" "/calculate/debt-payoff" \
+    '{"balance": -1, "interest_rate": 18, "monthly_payment": 200, "extra_payment": 0, "currency": "EUR"}' 400
+test_status "Debt Strategy (rate above 100)" "/calculate/debt-strategy" \
+    '{"debts": [{"balance": 1000, "rate": 120, "min_payment": 50}], "extra_payment": 100, "currency": "EUR"}' 400
+test_status "Emergency Fund (negative savings)" "/calculate/emergency-fund" \
+    '{"monthly_expenses": 1000, "months_coverage": 6, "current_savings": -1, "monthly_contribution": 100, "currency": "EUR"}' 400
+test_status "Tax (rate above 100)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 150, "currency": "UAH"}' 400
+test_status "Buy vs Rent (negative rent)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": -1, "rent_growth": 3, "property_growth": 2, "horizon": 10, "currency": "EUR"}' 400
+test_status "Savings Rate (negative expenses)" "/calculate/savings-rate" \
+    '{"monthly_income": 3000, "monthly_expenses": -1, "currency": "EUR"}' 400
+test_status "Tax (rate of exactly 100)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 100, "currency": "UAH"}' 200
+
+# 33. FIRE projection
+test_endpoint "FIRE" "/calculate/fire" \
+    '{"current_net_worth": 100000, "annual_expenses": 40000, "annual_savings": 50000, "expected_return": 5, "current_age": 30, "currency": "EUR"}'
+test_contains "FIRE (projected age)" "/calculate/fire" \
+    '{"current_net_worth": 100000, "annual_expenses": 40000, "annual_savings": 50000, "expected_return": 5, "current_age": 30, "currency": "EUR"}' \
+    '"fire_age":42.2'
+
+# 34. Gzip only when the client asks for it, body round-trips either way
+echo "Testing Gzip..."
+payload='{"amount": 5000, "rate": 12, "term": 3, "currency": "USD"}'
+plain=$(curl -s -X POST "$BASE_URL/calculate/credit" -H "Content-Type: application/json" -d "$payload")
+encoding=$(curl -s -o /dev/null -D - -X POST "$BASE_URL/calculate/credit" \
+    -H "Content-Type: application/json" -H "Accept-Encoding: gzip" -d "$payload" | grep -i "^content-encoding")
+decoded=$(curl -s --compressed -X POST "$BASE_URL/calculate/credit" -H "Content-Type: application/json" -d "$payload")
+if echo "$encoding" | grep -qi "gzip" && [ "$plain" = "$decoded" ]; then
+    echo "✅ Gzip: Success"
+else
+    echo "❌ Gzip: Failed"
+    echo "Content-Encoding: $encoding"
+fi
+echo "-----------------------------------"
+
+# 35. Charts can be skipped
+test_contains "Credit (no chart)" "/calculate/credit" \
+    '{"amount": 5000, "rate": 12, "term": 3, "currency": "USD", "include_chart": false}' \
+    '"chart":""'
+test_contains "FIRE (no chart)" "/calculate/fire" \
+    '{"current_net_worth": 100000, "annual_expenses": 40000, "annual_savings": 50000, "expected_return": 5, "currency": "EUR", "include_chart": false}' \
+    '"chart":""'
+test_contains "Investment (no charts)" "/calculate/investment" \
+    '{"initial_amount": 10000, "monthly_contribution": 500, "annual_return": 8, "period": 10, "currency": "USD", "include_growth_chart": true, "include_chart": false}' \
+    '"chart":""'
+
+# 36. Compound interest on a lump sum
+test_endpoint "Compound" "/calculate/compound" \
+    '{"principal": 1000, "annual_rate": 10, "years": 10, "compounding": "annual", "currency": "EUR"}'
+test_contains "Compound (amount)" "/calculate/compound" \
+    '{"principal": 1000, "annual_rate": 10, "years": 10, "compounding": "annual", "currency": "EUR"}' \
+    '"final_amount":2593.74'
+test_contains "Compound (zero years)" "/calculate/compound" \
+    '{"principal": 1000, "annual_rate": 10, "years": 0, "currency": "EUR"}' \
+    '"final_amount":1000.0'
+
+# 37. Credit schedule as CSV (header + one row per month, five columns)
+echo "Testing Credit (CSV)..."
+csv=$(curl -s -X POST "$BASE_URL/calculate/credit?format=csv" -H "Content-Type: application/json" \
+    -d '{"amount": 5000, "rate": 12, "term": 3, "currency": "USD"}')
+rows=$(echo "$csv" | wc -l)
+columns=$(echo "$csv" | awk -F, '{ print NF }' | sort -u)
+if [ "$rows" = "37" ] && [ "$columns" = "5" ]; then
+    echo "✅ Credit (CSV): $rows rows"
+else
+    echo "❌ Credit (CSV): Failed"
+    echo "Response: $csv"
+fi
+echo "-----------------------------------"
+
+# 38. Dark chart theme
+test_contains "Tax (dark theme)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "theme": "dark"}' \
+    'height=\\"100%\\" fill=\\"#17212b\\"'
+
+# 39. Freelance rate (36000 net + 4000 expenses at 20% tax = 50000 gross over 1200 hours)
+test_endpoint "Freelance Rate" "/calculate/freelance-rate" \
+    '{"desired_net_income": 36000, "billable_hours": 1200, "business_expenses": 4000, "tax_rate": 20, "currency": "EUR"}'
+test_contains "Freelance Rate (hourly)" "/calculate/freelance-rate" \
+    '{"desired_net_income": 36000, "billable_hours": 1200, "business_expenses": 4000, "tax_rate": 20, "currency": "EUR"}' \
+    '"hourly_rate":41.67'
+test_status "Freelance Rate (no billable hours)" "/calculate/freelance-rate" \
+    '{"desired_net_income": 36000, "billable_hours": 0, "business_expenses": 4000, "tax_rate": 20, "currency": "EUR"}' 400
+
+# 40. Bar charts draw four grid lines
+echo "Testing Tax (grid lines)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" \
+    -d '{"income": 50000, "tax_rate": 18, "currency": "UAH"}')
+lines=$(echo "$response" | grep -o "<line" | wc -l)
+if [ "$lines" = "4" ]; then
+    echo "✅ Tax (grid lines): $lines"
+else
+    echo "❌ Tax (grid lines): expected 4, got $lines"
+fi
+echo "-----------------------------------"
+
+# 41. Wage conversion in both directions (52000 a year = 25 an hour at 40h x 52 weeks)
+test_contains "Wage Convert (salary to hourly)" "/calculate/wage-convert" \
+    '{"annual_salary": 52000, "hours_per_week": 40, "weeks_per_year": 52, "currency": "USD"}' \
+    '"hourly":25.0'
+test_contains "Wage Convert (hourly to salary)" "/calculate/wage-convert" \
+    '{"hourly_rate": 25, "hours_per_week": 40, "weeks_per_year": 52, "currency": "USD"}' \
+    '"annual":52000.0'
+test_status "Wage Convert (neither input)" "/calculate/wage-convert" \
+    '{"hours_per_week": 40, "weeks_per_year": 52, "currency": "USD"}' 400
+test_status "Wage Convert (both inputs)" "/calculate/wage-convert" \
+    '{"annual_salary": 52000, "hourly_rate": 25, "hours_per_week": 40, "weeks_per_year": 52, "currency": "USD"}' 400
+
+# 42. Formatted headline amount follows the currency's convention
+test_contains "Tax (formatted USD)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "USD"}' \
+    '"formatted_net_income":"\$41,000.00"'
+test_contains "Tax (formatted UAH)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH"}' \
+    '"formatted_net_income":"41 000,00 ₴"'
+
+# 43. Time value adjusted for purchasing power
+test_contains "Time Value (PPP)" "/calculate/time-value" \
+    '{"annual_income": 50000, "annual_hours": 2000, "ppp_factor": 0.4, "currency": "EUR"}' \
+    '"ppp_adjusted_value":10.0'
+
+# 44. Refinance: pays off in month 26, or never when the rate barely drops
+test_contains "Refinance (break-even)" "/calculate/refinance" \
+    '{"current_balance": 200000, "current_rate": 5, "remaining_term": 20, "new_rate": 3.5, "new_term": 20, "closing_costs": 4000, "currency": "EUR"}' \
+    '"break_even_month":26'
+test_contains "Refinance (no break-even)" "/calculate/refinance" \
+    '{"current_balance": 200000, "current_rate": 5, "remaining_term": 20, "new_rate": 4.9, "new_term": 20, "closing_costs": 10000, "currency": "EUR"}' \
+    '"break_even_month":null'
+
+# 45. Investment return scenarios are ordered low < expected < high
+echo "Testing Investment (scenarios)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/investment" -H "Content-Type: application/json" \
+    -d '{"initial_amount": 10000, "monthly_contribution": 500, "annual_return": 7, "return_low": 3, "return_high": 11, "period": 10, "currency": "USD"}')
+scenarios=$(echo "$response" | grep -o '"scenarios":{[^}]*}')
+low=$(echo "$scenarios" | grep -o '"low":[0-9.]*' | cut -d: -f2)
+expected=$(echo "$scenarios" | grep -o '"expected":[0-9.]*' | cut -d: -f2)
+high=$(echo "$scenarios" | grep -o '"high":[0-9.]*' | cut -d: -f2)
+if awk -v l="$low" -v e="$expected" -v h="$high" 'BEGIN { exit !(h > e && e > l) }'; then
+    echo "✅ Investment (scenarios): $low < $expected < $high"
+else
+    echo "❌ Investment (scenarios): Failed"
+    echo "Response: $scenarios"
+fi
+echo "-----------------------------------"
+
+# 46. Monte Carlo retirement is reproducible for a given seed
+echo "Testing Retirement Monte Carlo..."
+payload='{"current_age": 30, "retirement_age": 60, "desired_income": 2000, "current_savings": 10000, "monthly_savings": 500, "expected_return": 7, "volatility": 15, "trials": 1000, "seed": 1, "currency": "EUR"}'
+first=$(curl -s -X POST "$BASE_URL/calculate/retirement-monte-carlo" -H "Content-Type: application/json" -d "$payload")
+second=$(curl -s -X POST "$BASE_URL/calculate/retirement-monte-carlo" -H "Content-Type: application/json" -d "$payload")
+if echo "$first" | grep -q '"success_probability"' && [ "$first" = "$second" ]; then
+    echo "✅ Retirement Monte Carlo: Reproducible"
+else
+    echo "❌ Retirement Monte Carlo: Failed"
+    echo "Response: $first"
+fi
+echo "-----------------------------------"
+
+# 47. Oversized bodies and batches are refused
+padding=$(head -c 70000 /dev/zero | tr '\0' 'x')
+test_status "Tax (oversized body)" "/calculate/tax" \
+    "{\"income\": 50000, \"tax_rate\": 18, \"currency\": \"UAH\", \"padding\": \"$padding\"}" 413
+items=$(for i in $(seq 51); do printf '{"type": "tax", "params": {"income": 1000, "tax_rate": 18, "currency": "UAH"}},'; done)
+test_status "Batch (too many items)" "/calculate/batch" "[${items%,}]" 413
+
+# 48. Investment in today's money (3% inflation over 10 years)
+test_contains "Investment (real return)" "/calculate/investment" \
+    '{"initial_amount": 10000, "monthly_contribution": 0, "annual_return": 7, "inflation_rate": 3, "period": 10, "compounding": "annual", "currency": "USD"}' \
+    '"real_future_value":14637.45'
+
+# 49. Wrong method on a known route is 405 with Allow, unknown paths stay 404
+echo "Testing Method Not Allowed..."
+headers=$(curl -s -o /dev/null -D - "$BASE_URL/calculate/credit")
+status=$(curl -s -o /dev/null -w "%{http_code}" "$BASE_URL/calculate/credit")
+if [ "$status" = "405" ] && echo "$headers" | grep -qi "^allow: POST, OPTIONS"; then
+    echo "✅ Method Not Allowed: $status"
+else
+    echo "❌ Method Not Allowed: $status"
+    echo "Headers: $headers"
+fi
+echo "-----------------------------------"
+test_status "Unknown path" "/calculate/does-not-exist" '{}' 404
+
+# 50. Retirement stacked bar: segment heights add up to the bar height
+echo "Testing Retirement (stacked bar)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/retirement" -H "Content-Type: application/json" \
+    -d '{"current_age": 30, "retirement_age": 60, "desired_income": 2000, "current_savings": 10000, "monthly_savings": 500, "expected_return": 7, "currency": "EUR"}')
+total=$(echo "$response" | grep -o 'data-bar=\\"0\\" data-height=\\"[0-9]*' | grep -o '[0-9]*$')
+segments=$(echo "$response" | grep -o 'data-bar=\\"0\\" height=\\"[0-9]*' | grep -o '[0-9]*$' | paste -sd+ | bc)
+if [ -n "$total" ] && [ "$total" = "$segments" ]; then
+    echo "✅ Retirement (stacked bar): $segments = $total"
+else
+    echo "❌ Retirement (stacked bar): segments $segments, bar $total"
+fi
+echo "-----------------------------------"
+
+# 51. Months until the down payment is saved
+test_contains "Buy vs Rent (down payment saved)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": 1000, "rent_growth": 3, "property_growth": 2, "horizon": 10, "current_down_savings": 50000, "currency": "EUR"}' \
+    '"months_to_down_payment":0.0'
+test_contains "Buy vs Rent (still saving)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": 1000, "rent_growth": 3, "property_growth": 2, "horizon": 10, "current_down_savings": 20000, "monthly_down_contribution": 1000, "currency": "EUR"}' \
+    '"months_to_down_payment":30.0'
+
+# 52. A lower withdrawal rate needs more capital than the default 4% rule
+test_contains "Retirement (4% withdrawal)" "/calculate/retirement" \
+    '{"current_age": 30, "retirement_age": 65, "current_savings": 5000, "monthly_savings": 500, "expected_return": 7, "desired_income": 2000, "currency": "EUR"}' \
+    '"required_capital":600000.0'
+test_contains "Retirement (3% withdrawal)" "/calculate/retirement" \
+    '{"current_age": 30, "retirement_age": 65, "current_savings": 5000, "monthly_savings": 500, "expected_return": 7, "desired_income": 2000, "withdrawal_rate": 3, "currency": "EUR"}' \
+    '"required_capital":800000.0'
+test_status "Retirement (zero withdrawal rate)" "/calculate/retirement" \
+    '{"current_age": 30, "retirement_age": 65, "current_savings": 5000, "monthly_savings": 500, "expected_return": 7, "desired_income": 2000, "withdrawal_rate": 0, "currency": "EUR"}' 400
+
+# 53. Opportunity cost: 10,000 at 7% for 10 years grows by the factor 1.07^10 = 1.967151
+test_endpoint "Opportunity Cost" "/calculate/opportunity-cost" \
+    '{"amount": 10000, "annual_return": 7, "years": 10, "currency": "USD"}'
+test_contains "Opportunity Cost (future value)" "/calculate/opportunity-cost" \
+    '{"amount": 10000, "annual_return": 7, "years": 10, "currency": "USD"}' \
+    '"future_value":19671.51'
+test_contains "Opportunity Cost (lost gain)" "/calculate/opportunity-cost" \
+    '{"amount": 10000, "annual_return": 7, "years": 10, "currency": "USD"}' \
+    '"lost_gain":9671.51'
+
+# 54. Repeating a request with its ETag is answered with 304
+echo "Testing Tax (ETag)..."
+payload='{"income": 50000, "tax_rate": 18, "currency": "UAH"}'
+etag=$(curl -s -D - -o /dev/null -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" -d "$payload" \
+    | grep -i "^etag:" | cut -d' ' -f2- | tr -d '\r')
+status=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" \
+    -H "If-None-Match: $etag" -d "$payload")
+if [ -n "$etag" ] && [ "$status" = "304" ]; then
+    echo "✅ Tax (ETag): $etag -> $status"
+else
+    echo "❌ Tax (ETag): expected 304, got $status (ETag: $etag)"
+fi
+echo "-----------------------------------"
+
+# 55. Biweekly payments cost less interest than monthly ones
+echo "Testing Credit (biweekly)..."
+monthly=$(curl -s -X POST "$BASE_URL/calculate/credit" -H "Content-Type: application/json" \
+    -d '{"amount": 200000, "rate": 5, "term": 30, "currency": "EUR"}' \
+    | grep -o '"overpayment":[0-9.]*' | cut -d: -f2)
+biweekly=$(curl -s -X POST "$BASE_URL/calculate/credit" -H "Content-Type: application/json" \
+    -d '{"amount": 200000, "rate": 5, "term": 30, "payment_frequency": "biweekly", "currency": "EUR"}' \
+    | grep -o '"overpayment":[0-9.]*' | cut -d: -f2)
+if awk -v m="$monthly" -v b="$biweekly" 'BEGIN { exit !(m > b && b > 0) }'; then
+    echo "✅ Credit (biweekly): $biweekly < $monthly"
+else
+    echo "❌ Credit (biweekly): monthly $monthly, biweekly $biweekly"
+fi
+echo "-----------------------------------"
+test_status "Credit (unknown frequency)" "/calculate/credit" \
+    '{"amount": 200000, "rate": 5, "term": 30, "payment_frequency": "weekly", "currency": "EUR"}' 400
+
+# 56. Goal progress: already complete, on track, and never reached without growth
+test_contains "Goal Progress (complete)" "/calculate/goal-progress" \
+    '{"target_amount": 10000, "current_amount": 12000, "monthly_contribution": 100, "currency": "EUR"}' \
+    '"months_remaining":0.0'
+test_contains "Goal Progress (on track)" "/calculate/goal-progress" \
+    '{"target_amount": 10000, "current_amount": 4000, "monthly_contribution": 500, "currency": "EUR"}' \
+    '"months_remaining":12.0'
+test_contains "Goal Progress (with growth)" "/calculate/goal-progress" \
+    '{"target_amount": 10000, "current_amount": 4000, "monthly_contribution": 500, "annual_return": 6, "currency": "EUR"}' \
+    '"months_remaining":11.2'
+test_contains "Goal Progress (never)" "/calculate/goal-progress" \
+    '{"target_amount": 10000, "current_amount": 4000, "monthly_contribution": 0, "currency": "EUR"}' \
+    '"months_remaining":-1.0'
+
+# 57. Schema describes every calculator's fields
+echo "Testing Schema..."
+response=$(curl -s "$BASE_URL/schema")
+if echo "$response" | grep -q '"tax":{"request":\[{"name":"income","required":true,"type":"number"}'; then
+    echo "✅ Schema: OK"
+else
+    echo "❌ Schema: Failed"
+    echo "Response: ${response:0:200}"
+fi
+echo "-----------------------------------"
+
+# 58. Posting without a body names the endpoint and the expected content type
+echo "Testing Tax (empty body)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/tax")
+if echo "$response" | grep -q "Request body required for /calculate/tax"; then
+    echo "✅ Tax (empty body): OK"
+else
+    echo "❌ Tax (empty body): Failed"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 59. Tax-free allowance: only income above it is taxed
+test_contains "Tax (below allowance)" "/calculate/tax" \
+    '{"income": 10000, "tax_rate": 20, "allowance": 12000, "currency": "EUR"}' \
+    '"tax_amount":0.0'
+test_contains "Tax (at allowance)" "/calculate/tax" \
+    '{"income": 12000, "tax_rate": 20, "allowance": 12000, "currency": "EUR"}' \
+    '"tax_amount":0.0'
+test_contains "Tax (above allowance)" "/calculate/tax" \
+    '{"income": 20000, "tax_rate": 20, "allowance": 12000, "currency": "EUR"}' \
+    '"effective_rate":8.0,"formatted_net_income":"€18,400.00","net_income":18400.0,"tax_amount":1600.0'
+
+# 60. Grouped scenario chart: one bar per category and series, each series in the legend
+echo "Testing Investment (grouped scenario chart)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/investment" -H "Content-Type: application/json" \
+    -d '{"initial_amount": 10000, "monthly_contribution": 500, "annual_return": 7, "return_low": 3, "return_high": 11, "period": 10, "lang": "en", "currency": "USD"}')
+bars=$(echo "$response" | grep -o "data-series=" | wc -l)
+if [ "$bars" = "6" ] && echo "$response" | grep -q ">Contributed<" && echo "$response" | grep -q ">Final value<"; then
+    echo "✅ Investment (grouped scenario chart): $bars bars"
+else
+    echo "❌ Investment (grouped scenario chart): expected 6 bars and both series in the legend, got $bars"
+fi
+echo "-----------------------------------"
+
+# 61. Subscription value: break-even at 5 uses, a loss at 1 use
+test_contains "Subscription (break-even)" "/calculate/subscription" \
+    '{"monthly_cost": 15, "uses_per_month": 5, "per_use_price": 3, "currency": "USD"}' \
+    '"monthly_savings":0.0'
+test_contains "Subscription (not worth it)" "/calculate/subscription" \
+    '{"monthly_cost": 15, "uses_per_month": 1, "per_use_price": 3, "currency": "USD"}' \
+    '"monthly_savings":-12.0'
+test_status "Subscription (never used)" "/calculate/subscription" \
+    '{"monthly_cost": 15, "uses_per_month": 0, "currency": "USD"}' 400
+
+# 62. Successful calculations report their timings
+echo "Testing Tax (Server-Timing)..."
+headers=$(curl -s -D - -o /dev/null -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" \
+    -d '{"income": 50000, "tax_rate": 18, "currency": "UAH"}')
+if echo "$headers" | grep -qi "^server-timing: calc;dur=[0-9]*, serialize;dur=[0-9]*"; then
+    echo "✅ Tax (Server-Timing): OK"
+else
+    echo "❌ Tax (Server-Timing): header missing"
+    echo "Headers: $headers"
+fi
+echo "-----------------------------------"
+
+# 63. Raises below inflation shrink the real salary
+test_contains "Wage Erosion (shrinks)" "/calculate/wage-erosion" \
+    '{"current_salary": 50000, "annual_raise": 2, "inflation_rate": 5, "horizon": 10, "currency": "EUR"}' \
+    '"real_change":-25.16,"real_trend":"shrinks"'
+test_contains "Wage Erosion (grows)" "/calculate/wage-erosion" \
+    '{"current_salary": 50000, "annual_raise": 5, "inflation_rate": 2, "horizon": 10, "currency": "EUR"}' \
+    '"real_trend":"grows"'
+
+# 64. Every supported currency is listed with its formatting metadata
+echo "Testing Currencies..."
+response=$(curl -s "$BASE_URL/currencies")
+missing=""
+for code in EUR USD UAH BTC; do
+    echo "$response" | grep -q "\"code\":\"$code\"" || missing="$missing $code"
+done
+if [ -z "$missing" ] && echo "$response" | grep -q '"code":"UAH","symbol":"₴","symbol_position":"after","precision":2'; then
+    echo "✅ Currencies: OK"
+else
+    echo "❌ Currencies: missing$missing"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 65. A payment is checked against the month's interest the same way everywhere: below it is refused,
+# naming the payment. A 100-year mortgage at 100% is all interest but still computed
+test_status "Buy vs Rent (interest-only mortgage)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 100, "mortgage_term": 100, "monthly_rent": 1000, "rent_growth": 3, "property_growth": 2, "horizon": 10, "currency": "EUR"}' 200
+test_status "Debt Strategy (payments below interest)" "/calculate/debt-strategy" \
+    '{"debts": [{"balance": 10000, "rate": 24, "min_payment": 100}], "extra_payment": 0, "currency": "EUR"}' 400
+test_contains "Debt Strategy (payments below interest message)" "/calculate/debt-strategy" \
+    '{"debts": [{"balance": 10000, "rate": 24, "min_payment": 100}], "extra_payment": 0, "currency": "EUR"}' \
+    'min_payment must be at least the monthly interest of 200"'
+
+# 66. Net pay: tax, contributions, deductions and net pay add up to the gross salary
+echo "Testing Net Pay..."
+response=$(curl -s -X POST "$BASE_URL/calculate/net-pay" -H "Content-Type: application/json" \
+    -d '{"gross_salary": 3333.33, "income_tax_rate": 18, "social_contribution_rate": 1.5, "fixed_deductions": 25, "currency": "UAH"}')
+field() { echo "$response" | grep -o "\"$1\":[0-9.-]*" | cut -d: -f2; }
+sum=$(awk -v a="$(field net_pay)" -v b="$(field income_tax)" -v c="$(field social_contributions)" -v d="$(field fixed_deductions)" \
+    'BEGIN { printf "%.2f", a + b + c + d }')
+if [ "$sum" = "3333.33" ]; then
+    echo "✅ Net Pay: parts add up to $sum"
+else
+    echo "❌ Net Pay: parts add up to $sum, expected 3333.33"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+test_contains "Net Pay (amount)" "/calculate/net-pay" \
+    '{"gross_salary": 5000, "income_tax_rate": 18, "social_contribution_rate": 1.5, "fixed_deductions": 100, "currency": "EUR"}' \
+    '"net_pay":3925.0'
+
+# 67. Negative returns compound instead of falling back to a plain sum
+test_contains "Investment (negative return)" "/calculate/investment" \
+    '{"initial_amount": 10000, "monthly_contribution": 100, "annual_return": -2, "period": 10, "compounding": "annual", "currency": "EUR"}' \
+    '"future_value":19146.36'
+
+# 68. Required savings round-trip: saving the result monthly reaches the target
+echo "Testing Required Savings (round trip)..."
+monthly=$(curl -s -X POST "$BASE_URL/calculate/required-savings" -H "Content-Type: application/json" \
+    -d '{"target_amount": 500000, "years": 30, "annual_return": 7, "current_savings": 10000, "currency": "EUR"}' \
+    | grep -o '"monthly_savings":[0-9.]*' | cut -d: -f2)
+future=$(curl -s -X POST "$BASE_URL/calculate/retirement" -H "Content-Type: application/json" \
+    -d "{\"current_age\": 30, \"retirement_age\": 60, \"desired_income\": 2000, \"current_savings\": 10000, \"monthly_savings\": $monthly, \"expected_return\": 7, \"currency\": \"EUR\"}" \
+    | grep -o '"future_value":[0-9.]*' | cut -d: -f2)
+# Rounding the payment to cents moves the result by a few euros at most
+if awk -v f="$future" 'BEGIN { d = f - 500000; exit !(d > -10 && d < 10) }'; then
+    echo "✅ Required Savings (round trip): $monthly a month -> $future"
+else
+    echo "❌ Required Savings (round trip): $monthly a month -> $future, expected about 500000"
+fi
+echo "-----------------------------------"
+test_contains "Required Savings (zero return)" "/calculate/required-savings" \
+    '{"target_amount": 12000, "years": 1, "annual_return": 0, "currency": "EUR"}' \
+    '"monthly_savings":1000.0'
+
+# 69. Chart value labels are grouped and abbreviated per language
+test_contains "Chart labels (en)" "/calculate/opportunity-cost" \
+    '{"amount": 1500000, "annual_return": 0, "years": 1, "currency": "EUR", "lang": "en"}' \
+    '>1.5M<'
+test_contains "Chart labels (uk)" "/calculate/opportunity-cost" \
+    '{"amount": 12345, "annual_return": 0, "years": 1, "currency": "EUR", "lang": "uk"}' \
+    '>12 345<'
+
+# 70. A term too long to schedule is refused up front instead of crashing the worker
+test_status "Credit (term of a million years)" "/calculate/credit" \
+    '{"amount": 1000, "rate": 5, "term": 1000000, "currency": "EUR"}' 400
+test_contains "Credit (term of a million years, field)" "/calculate/credit" \
+    '{"amount": 1000, "rate": 5, "term": 1000000, "currency": "EUR"}' \
+    '"field":"term"'
+
+# 71. Debt vs invest: an expensive debt is worth paying off first
+test_contains "Debt vs Invest (high debt rate)" "/calculate/debt-vs-invest" \
+    '{"debt_balance": 10000, "debt_rate": 24, "investment_return": 7, "monthly_amount": 500, "horizon": 5, "currency": "EUR"}' \
+    '"recommendation":"pay_debt"'
+test_contains "Debt vs Invest (cheap debt)" "/calculate/debt-vs-invest" \
+    '{"debt_balance": 10000, "debt_rate": 3, "investment_return": 10, "monthly_amount": 500, "horizon": 5, "currency": "EUR"}' \
+    '"recommendation":"invest"'
+
+# 72. CORS origin. Without ALLOWED_ORIGINS every origin gets "*"; to check the
+# allow-list, start the worker with e.g.
+#   wrangler dev --var ALLOWED_ORIGINS:https://app.example
+# and run this script with ALLOWED_ORIGINS=https://app.example
+cors_header() {
+    curl -s -D - -o /dev/null -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" \
+        -H "Origin: $1" -d '{"income": 50000, "tax_rate": 18, "currency": "UAH"}' \
+        | grep -i "^access-control-allow-origin:" | cut -d' ' -f2 | tr -d '\r'
+}
+check_cors() {
+    local name=$1
+    local actual=$2
+    local expected=$3
+    if [ "$actual" = "$expected" ]; then
+        echo "✅ CORS ($name): \"$actual\""
+    else
+        echo "❌ CORS ($name): Expected \"$expected\", got \"$actual\""
+    fi
+    echo "-----------------------------------"
+}
+if [ -z "$ALLOWED_ORIGINS" ]; then
+    check_cors "wildcard" "$(cors_header https://anywhere.example)" "*"
+else
+    allowed=${ALLOWED_ORIGINS%%,*}
+    check_cors "allowed" "$(cors_header "$allowed")" "$allowed"
+    check_cors "disallowed" "$(cors_header https://elsewhere.example)" ""
+fi
+
+# 73. Lump sum vs DCA: with steadily rising prices investing at once wins
+test_contains "Lump vs DCA (rising market)" "/calculate/lump-vs-dca" \
+    '{"total_amount": 12000, "dca_periods": 12, "annual_return": 8, "horizon": 10, "currency": "EUR"}' \
+    '"difference":949.07'
+test_status "Lump vs DCA (periods beyond horizon)" "/calculate/lump-vs-dca" \
+    '{"total_amount": 12000, "dca_periods": 24, "annual_return": 8, "horizon": 1, "currency": "EUR"}' 400
+
+# 74. The colorblind palette swaps green/red for blue/orange
+test_contains "Tax (colorblind palette, positive)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "palette": "colorblind"}' \
+    'fill=\\"#0072b2\\"'
+test_contains "Tax (colorblind palette, negative)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "palette": "colorblind"}' \
+    'fill=\\"#e69f00\\"'
+
+# 75. Request IDs: a supplied one is echoed, otherwise a UUID is generated, errors included
+request_id_header() {
+    curl -s -D - -o /dev/null "$@" | grep -i "^x-request-id:" | cut -d' ' -f2 | tr -d '\r'
+}
+check_request_id() {
+    local name=$1
+    local actual=$2
+    local pattern=$3
+    if echo "$actual" | grep -Eq "$pattern"; then
+        echo "✅ Request ID ($name): $actual"
+    else
+        echo "❌ Request ID ($name): \"$actual\" does not match $pattern"
+    fi
+    echo "-----------------------------------"
+}
+uuid='^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$'
+check_request_id "supplied" "$(request_id_header -H "X-Request-ID: trace-abc-123" "$BASE_URL/health")" '^trace-abc-123$'
+check_request_id "generated" "$(request_id_header "$BASE_URL/health")" "$uuid"
+check_request_id "error" "$(request_id_header -X POST "$BASE_URL/calculate/does-not-exist" -d '{}')" "$uuid"
+
+# 76. Inflation both ways, 5% over 10 years
+test_contains "Inflation (future)" "/calculate/inflation" \
+    '{"amount": 1000, "inflation_rate": 5, "years": 10, "direction": "future", "currency": "EUR"}' \
+    '"adjusted_amount":1628.89'
+test_contains "Inflation (past)" "/calculate/inflation" \
+    '{"amount": 1000, "inflation_rate": 5, "years": 10, "direction": "past", "currency": "EUR"}' \
+    '"adjusted_amount":613.91'
+test_status "Inflation (unknown direction)" "/calculate/inflation" \
+    '{"amount": 1000, "inflation_rate": 5, "years": 10, "direction": "sideways", "currency": "EUR"}' 400
+
+# 77. The first payment's split adds up to the payment and matches the schedule's first row
+echo "Testing Credit (first payment split)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/credit" -H "Content-Type: application/json" \
+    -d '{"amount": 5000, "rate": 12, "term": 3, "currency": "USD", "include_schedule": true}')
+field() { echo "$response" | grep -o "\"$1\":[0-9.-]*" | head -1 | cut -d: -f2; }
+sum=$(awk -v a="$(field first_month_interest)" -v b="$(field first_month_principal)" 'BEGIN { printf "%.2f", a + b }')
+first_row='"interest":50.0,"month":1,"payment":166.07,"principal":116.07'
+if [ "$sum" = "$(field monthly_payment)" ] && echo "$response" | grep -q "$first_row"; then
+    echo "✅ Credit (first payment split): $(field first_month_interest) + $(field first_month_principal) = $sum"
+else
+    echo "❌ Credit (first payment split): parts add up to $sum, payment $(field monthly_payment)"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+test_contains "Credit (first payment split, fields)" "/calculate/credit" \
+    '{"amount": 5000, "rate": 12, "term": 3, "currency": "USD"}' \
+    '"first_month_interest":50.0,"first_month_principal":116.07'
+
+# 78. Job offers: the higher salary loses once the commute and expenses are counted
+test_contains "Job Compare (long commute loses)" "/calculate/job-compare" \
+    '{"offer_a": {"monthly_income": 4000, "taxes": 20, "work_hours": 160, "commute_time": 60, "work_expenses": 300}, "offer_b": {"monthly_income": 3500, "taxes": 20, "work_hours": 160, "commute_time": 0, "work_expenses": 50}, "currency": "EUR"}' \
+    '"better_offer":"b"'
+test_status "Job Compare (offer without hours)" "/calculate/job-compare" \
+    '{"offer_a": {"monthly_income": 4000, "taxes": 20, "work_hours": 0, "commute_time": 0, "work_expenses": 0}, "offer_b": {"monthly_income": 3500, "taxes": 20, "work_hours": 160, "commute_time": 0, "work_expenses": 50}, "currency": "EUR"}' 400
+
+# 79. Astronomical results are capped and flagged instead of sent as 4.85e+25
+test_contains "Investment (overflow flag)" "/calculate/investment" \
+    '{"initial_amount": 1000000000, "monthly_contribution": 0, "annual_return": 100, "period": 40, "currency": "EUR"}' \
+    '"overflow":true'
+test_contains "Investment (overflow capped)" "/calculate/investment" \
+    '{"initial_amount": 1000000000, "monthly_contribution": 0, "annual_return": 100, "period": 40, "currency": "EUR"}' \
+    '"future_value":1000000000000000.0'
+
+# 80. A daily coffee over 20 years, and what it would have grown into at 7%
+test_contains "Recurring Cost (daily coffee)" "/calculate/recurring-cost" \
+    '{"cost": 3.5, "frequency": "daily", "years": 20, "annual_return": 7, "currency": "EUR"}' \
+    '"annual_cost":1277.5'
+test_contains "Recurring Cost (daily coffee, invested)" "/calculate/recurring-cost" \
+    '{"cost": 3.5, "frequency": "daily", "years": 20, "annual_return": 7, "currency": "EUR"}' \
+    '"future_value":55456.98,"lost_gain":29906.98,"total_cost":25550.0'
+
+# 81. Currencies added to the table flow through to symbols, formatting and precision
+test_contains "Tax (GBP symbol)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "GBP", "strict_currency": true}' \
+    '"currency_symbol":"£"'
+test_contains "Tax (PLN formatting)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "PLN", "strict_currency": true}' \
+    '"formatted_net_income":"41 000,00 zł"'
+test_contains "Tax (JPY without decimals)" "/calculate/tax" \
+    '{"income": 1234567, "tax_rate": 10, "currency": "JPY", "strict_currency": true}' \
+    '"formatted_net_income":"¥1,111,110"'
+
+# 82. PNG charts come back as base64 data URLs ("iVBORw0KGgo" is the PNG signature)
+test_contains "Tax (PNG chart)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "chart_format": "png"}' \
+    '"chart":"data:image/png;base64,iVBORw0KGgo'
+test_status "Tax (unknown chart format)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "chart_format": "gif"}' 400
+
+# 83. Blended rate weights each debt's rate by its balance: (5000*20 + 3000*10 + 2000*5) / 10000
+test_contains "Blended Rate" "/calculate/blended-rate" \
+    '{"debts": [{"balance": 5000, "rate": 20, "min_payment": 100}, {"balance": 3000, "rate": 10, "min_payment": 50}, {"balance": 2000, "rate": 5, "min_payment": 40}], "currency": "EUR"}' \
+    '"blended_rate":14.0'
+test_status "Blended Rate (no debts)" "/calculate/blended-rate" \
+    '{"debts": [], "currency": "EUR"}' 400
+
+# 84. Each chart's root <svg> carries an id hashed from its content, so two charts on one page don't collide
+chart_id() {
+    curl -s -X POST "$BASE_URL$1" -H "Content-Type: application/json" -d "$2" | grep -o 'svg id=\\"chart-[0-9a-f]*' | grep -o 'chart-[0-9a-f]*'
+}
+echo "Testing Chart IDs..."
+tax_id=$(chart_id "/calculate/tax" '{"income": 50000, "tax_rate": 18, "currency": "UAH"}')
+credit_id=$(chart_id "/calculate/credit" '{"amount": 5000, "rate": 5, "term": 3, "currency": "USD"}')
+repeat_id=$(chart_id "/calculate/tax" '{"income": 50000, "tax_rate": 18, "currency": "UAH"}')
+if [ -n "$tax_id" ] && [ -n "$credit_id" ] && [ "$tax_id" != "$credit_id" ] && [ "$tax_id" = "$repeat_id" ]; then
+    echo "✅ Chart IDs: $tax_id and $credit_id differ, repeat is stable"
+else
+    echo "❌ Chart IDs: tax \"$tax_id\", credit \"$credit_id\", repeated tax \"$repeat_id\""
+fi
+echo "-----------------------------------"
+
+# 85. Current coverage is savings divided by monthly expenses, left out when there are no expenses
+test_contains "Emergency Fund (current coverage)" "/calculate/emergency-fund" \
+    '{"monthly_expenses": 1500, "months_coverage": 6, "current_savings": 4500, "monthly_contribution": 200, "currency": "EUR"}' \
+    '"current_coverage_months":3.0'
+echo "Testing Emergency Fund (no expenses)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/emergency-fund" -H "Content-Type: application/json" \
+    -d '{"monthly_expenses": 0, "months_coverage": 6, "current_savings": 4500, "monthly_contribution": 200, "currency": "EUR"}')
+if echo "$response" | grep -q '"target_amount"' && ! echo "$response" | grep -q 'current_coverage_months'; then
+    echo "✅ Emergency Fund (no expenses): coverage omitted"
+else
+    echo "❌ Emergency Fund (no expenses): unexpected response"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 86. Savings accounts are ranked by final balance, the higher APY first even when sent second
+test_contains "Savings Compare (best account)" "/calculate/savings-compare" \
+    '{"principal": 10000, "monthly_deposit": 100, "years": 5, "accounts": [{"name": "Bank A", "apy": 3}, {"name": "Bank B", "apy": 4.5}], "currency": "EUR"}' \
+    '"best_account":"Bank B","best_balance":19161.0'
+test_contains "Savings Compare (ranking)" "/calculate/savings-compare" \
+    '{"principal": 10000, "monthly_deposit": 100, "years": 5, "accounts": [{"name": "Bank A", "apy": 3}, {"name": "Bank B", "apy": 4.5}], "currency": "EUR"}' \
+    '"accounts":\[{"apy":4.5,"final_balance":19161.0,"interest_earned":3161.0,"name":"Bank B","rank":1},{"apy":3.0,"final_balance":18050.84,"interest_earned":2050.84,"name":"Bank A","rank":2}\]'
+test_status "Savings Compare (no accounts)" "/calculate/savings-compare" \
+    '{"principal": 10000, "monthly_deposit": 100, "years": 5, "accounts": [], "currency": "EUR"}' 400
+
+# 87. A period of 0 returns the initial amount with no gain; negative periods are refused
+test_contains "Investment (period 0, value)" "/calculate/investment" \
+    '{"initial_amount": 1000, "monthly_contribution": 200, "annual_return": 8, "period": 0, "currency": "EUR"}' \
+    '"future_value":1000.0'
+test_contains "Investment (period 0, no gain)" "/calculate/investment" \
+    '{"initial_amount": 1000, "monthly_contribution": 200, "annual_return": 8, "period": 0, "currency": "EUR"}' \
+    '"roi":0.0,"total_contributions":1000.0,"total_gain":0.0'
+test_status "Investment (period -1)" "/calculate/investment" \
+    '{"initial_amount": 1000, "monthly_contribution": 200, "annual_return": 8, "period": -1, "currency": "EUR"}' 400
+test_contains "Investment (period -1, field)" "/calculate/investment" \
+    '{"initial_amount": 1000, "monthly_contribution": 200, "annual_return": 8, "period": -1, "currency": "EUR"}' \
+    '"field":"period"'
+
+# 88. Round-ups: 40 transactions a month at 0.50 each, invested at 6% for 10 years
+test_contains "Round Up (savings)" "/calculate/round-up" \
+    '{"transactions_per_month": 40, "round_up": 0.5, "years": 10, "annual_return": 6, "currency": "EUR"}' \
+    '"annual_savings":240.0'
+test_contains "Round Up (invested)" "/calculate/round-up" \
+    '{"transactions_per_month": 40, "round_up": 0.5, "years": 10, "annual_return": 6, "currency": "EUR"}' \
+    '"future_value":3277.59,"investment_gain":877.59,"monthly_savings":20.0,"total_saved":2400.0'
+
+# 89. Data-only mode skips chart rendering, by header or query string, single and batch
+check_data_mode() {
+    local name=$1
+    shift
+    response=$(curl -s -X POST -H "Content-Type: application/json" "$@")
+    if echo "$response" | grep -q '"chart":""' && ! echo "$response" | grep -q '<svg'; then
+        echo "✅ Data Mode ($name): no chart"
+    else
+        echo "❌ Data Mode ($name): chart still rendered"
+        echo "Response: $response"
+    fi
+    echo "-----------------------------------"
+}
+check_data_mode "header" -H "X-Response-Mode: data" "$BASE_URL/calculate/tax" \
+    -d '{"income": 50000, "tax_rate": 18, "currency": "UAH"}'
+check_data_mode "query" "$BASE_URL/calculate/tax?chart=false" \
+    -d '{"income": 50000, "tax_rate": 18, "currency": "UAH"}'
+check_data_mode "batch" "$BASE_URL/calculate/batch?chart=false" \
+    -d '[{"type": "tax", "params": {"income": 50000, "tax_rate": 18, "currency": "UAH"}}]'
+
+# 90. Affordability: the largest loan, borrowed through the credit calculator, stays within the DTI limit
+echo "Testing Affordability (loan within DTI)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/affordability" -H "Content-Type: application/json" \
+    -d '{"monthly_income": 5000, "monthly_debt_payments": 500, "dti_ratio": 36, "mortgage_rate": 6, "term": 30, "currency": "EUR"}')
+field() { echo "$response" | grep -o "\"$1\":[0-9.-]*" | head -1 | cut -d: -f2; }
+max_payment=$(field max_monthly_payment)
+loan=$(field max_loan_amount)
+response=$(curl -s -X POST "$BASE_URL/calculate/credit" -H "Content-Type: application/json" \
+    -d "{\"amount\": $loan, \"rate\": 6, \"term\": 30, \"currency\": \"EUR\"}")
+payment=$(field monthly_payment)
+if [ "$max_payment" = "1300.0" ] && awk -v p="$payment" -v m="$max_payment" 'BEGIN { exit !(p <= m + 0.01) }'; then
+    echo "✅ Affordability: $loan costs $payment a month, limit $max_payment"
+else
+    echo "❌ Affordability: $loan costs $payment a month, limit $max_payment"
+fi
+echo "-----------------------------------"
+test_contains "Affordability (loan amount)" "/calculate/affordability" \
+    '{"monthly_income": 5000, "monthly_debt_payments": 500, "dti_ratio": 36, "mortgage_rate": 6, "term": 30, "currency": "EUR"}' \
+    '"max_loan_amount":216829.1'
+
+# 91. A high earner's marginal rate is the top band, well above the effective rate
+# (20000 at 10%, 30000 at 20% and 150000 at 45% is 75500 of 200000, 37.75%)
+test_contains "Tax (marginal rate)" "/calculate/tax" \
+    '{"income": 200000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": 50000, "rate": 20}, {"up_to": null, "rate": 45}], "currency": "UAH"}' \
+    '"marginal_rate":45.0'
+test_contains "Tax (effective rate)" "/calculate/tax" \
+    '{"income": 200000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": 50000, "rate": 20}, {"up_to": null, "rate": 45}], "currency": "UAH"}' \
+    '"effective_rate":37.8'
+
+# 92. Bill split: 100.45 doesn't divide by 4, so one share is a cent more and the shares add up
+test_contains "Bill Split (uneven)" "/calculate/bill-split" \
+    '{"bill_total": 87.35, "tip_percent": 15, "people": 4, "currency": "EUR"}' \
+    '"grand_total":100.45,"per_person":25.12,"shares":\[25.12,25.11,25.11,25.11\],"total_tip":13.1'
+echo "Testing Bill Split (shares add up)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/bill-split" -H "Content-Type: application/json" \
+    -d '{"bill_total": 100, "tip_percent": 0, "people": 3, "currency": "EUR"}')
+sum=$(echo "$response" | grep -o '"shares":\[[^]]*' | cut -d[ -f2 | tr ',' '\n' | awk '{ s += $1 } END { printf "%.2f", s }')
+if [ "$sum" = "100.00" ]; then
+    echo "✅ Bill Split (shares add up): $sum"
+else
+    echo "❌ Bill Split (shares add up): $sum, expected 100.00"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+test_contains "Bill Split (one person)" "/calculate/bill-split" \
+    '{"bill_total": 50, "tip_percent": 10, "people": 1, "currency": "EUR"}' \
+    '"shares":\[55.0\]'
+test_status "Bill Split (nobody)" "/calculate/bill-split" \
+    '{"bill_total": 50, "tip_percent": 10, "people": 0, "currency": "EUR"}' 400
+
+# 93. A requested chart size lands on the root <svg>, the drawing scales through its viewBox
+test_contains "Tax (chart 800x600)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "chart_width": 800, "chart_height": 600}' \
+    'width=\\"800\\" height=\\"600\\" viewBox=\\"0 0 400 300\\"'
+test_contains "Tax (chart size clamped)" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "chart_width": 10, "chart_height": 100000}' \
+    'width=\\"200\\" height=\\"1200\\"'
+
+# 94. The payoff timeline ends at the total interest: here interest outgrows the balance, so it
+# is also the chart's top value label
+echo "Testing Debt Payoff (timeline ends at total interest)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/debt-payoff" -H "Content-Type: application/json" \
+    -d '{"balance": 1000, "interest_rate": 24, "monthly_payment": 21, "extra_payment": 0, "currency": "EUR"}')
+total_interest=$(echo "$response" | grep -o '"total_interest":[0-9.]*' | cut -d: -f2)
+top_label=$(echo "$response" | sed 's/.*"timeline_chart"//' | grep -o 'text-anchor=\\"start\\">[0-9]*' | grep -o '[0-9]*$')
+if [ -n "$top_label" ] && [ "$top_label" = "$(printf '%.0f' "$total_interest")" ]; then
+    echo "✅ Debt Payoff (timeline): ends at $top_label, total interest $total_interest"
+else
+    echo "❌ Debt Payoff (timeline): top label \"$top_label\", total interest $total_interest"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 95. The series carries the numbers the bars were drawn from, and only on request
+echo "Testing Tax (series matches chart)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" \
+    -d '{"income": 50000, "tax_rate": 18, "currency": "UAH", "lang": "en", "include_series": true}')
+series=$(echo "$response" | grep -o '"series":\[[^]]*\]')
+net=$(echo "$response" | grep -o '"net_income":[0-9.]*' | cut -d: -f2)
+tax=$(echo "$response" | grep -o '"tax_amount":[0-9.]*' | cut -d: -f2)
+if [ "$series" = "\"series\":[{\"label\":\"Net\",\"value\":$net},{\"label\":\"Tax\",\"value\":$tax}]" ] \
+    && echo "$response" | grep -q 'bold\\" text-anchor=\\"middle\\">41,000<' \
+    && echo "$response" | grep -q 'bold\\" text-anchor=\\"middle\\">9,000<'; then
+    echo "✅ Tax (series): $series"
+else
+    echo "❌ Tax (series): $series"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+response=$(curl -s -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" \
+    -d '{"income": 50000, "tax_rate": 18, "currency": "UAH"}')
+if echo "$response" | grep -q '"tax_amount"' && ! echo "$response" | grep -q '"series"'; then
+    echo "✅ Tax (no series by default)"
+else
+    echo "❌ Tax (no series by default)"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 96. Mixed signs: the zero line sits at y=205, the negative bar (-31000) hangs below it and the
+# positive one (101000) ends on it (94 + 111)
+test_contains "Buy vs Rent (negative bar below zero)" "/calculate/buy-rent" \
+    '{"property_price": 100000, "down_payment": 100000, "mortgage_rate": 5, "mortgage_term": 20, "monthly_rent": 500, "rent_growth": 0, "property_growth": -30, "horizon": 1, "currency": "EUR", "lang": "en"}' \
+    '<rect x=\\"65\\" y=\\"205\\" width=\\"140\\" height=\\"34\\"'
+test_contains "Buy vs Rent (positive bar on zero)" "/calculate/buy-rent" \
+    '{"property_price": 100000, "down_payment": 100000, "mortgage_rate": 5, "mortgage_term": 20, "monthly_rent": 500, "rent_growth": 0, "property_growth": -30, "horizon": 1, "currency": "EUR", "lang": "en"}' \
+    '<rect x=\\"215\\" y=\\"94\\" width=\\"140\\" height=\\"111\\"'
+
+# 97. Raise impact, with the raise given either way but not both
+test_endpoint "Raise Impact" "/calculate/raise-impact" \
+    '{"current_salary": 50000, "raise_amount": 2500, "annual_raise": 0, "years": 30, "currency": "EUR"}'
+test_contains "Raise Impact (no raise growth)" "/calculate/raise-impact" \
+    '{"current_salary": 50000, "raise_amount": 2500, "annual_raise": 0, "years": 30, "currency": "EUR"}' \
+    '"lifetime_difference":75000.0'
+test_status "Raise Impact (amount and percent)" "/calculate/raise-impact" \
+    '{"current_salary": 50000, "raise_amount": 2500, "raise_percent": 5, "annual_raise": 0, "years": 30, "currency": "EUR"}' 400
+
+# 98. Budget shares must add up to 100
+test_endpoint "Budget" "/calculate/budget" \
+    '{"monthly_income": 3000, "currency": "EUR"}'
+test_status "Budget (shares add up to 110)" "/calculate/budget" \
+    '{"monthly_income": 3000, "needs_percent": 60, "currency": "EUR"}' 400
+test_status "Budget (shares add up to 90)" "/calculate/budget" \
+    '{"monthly_income": 3000, "needs_percent": 40, "wants_percent": 30, "savings_percent": 20, "currency": "EUR"}' 400
+
+# 99. A repeated Idempotency-Key replays the stored batch result (needs the IDEMPOTENCY namespace bound)
+echo "Testing Batch (idempotent replay)..."
+key="test-$(date +%s)-$RANDOM"
+batch='[{"type": "tax", "params": {"income": 50000, "tax_rate": 18, "currency": "UAH", "include_chart": false}}]'
+first=$(curl -s -X POST "$BASE_URL/calculate/batch" -H "Content-Type: application/json" \
+    -H "Idempotency-Key: $key" -d "$batch")
+second=$(curl -s -i -X POST "$BASE_URL/calculate/batch" -H "Content-Type: application/json" \
+    -H "Idempotency-Key: $key" -d "$batch" | tr -d '\r')
+if echo "$second" | grep -qi '^Idempotent-Replayed: true' && [ "$(echo "$second" | tail -1)" = "$first" ]; then
+    echo "✅ Batch (idempotent replay): $first"
+else
+    echo "❌ Batch (idempotent replay)"
+    echo "First: $first"
+    echo "Second: $second"
+fi
+echo "-----------------------------------"
+status=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/calculate/batch" -H "Content-Type: application/json" \
+    -H "Idempotency-Key: $key" -d '[{"type": "tax", "params": {"income": 1, "tax_rate": 18, "currency": "UAH"}}]')
+if [ "$status" = "422" ]; then
+    echo "✅ Batch (key reused for another batch): $status"
+else
+    echo "❌ Batch (key reused for another batch): Expected 422, got $status"
+fi
+echo "-----------------------------------"
+
+# 100. Cost of waiting; the delay can't outlast the horizon
+test_endpoint "Cost of Waiting" "/calculate/cost-of-waiting" \
+    '{"monthly_contribution": 500, "annual_return": 7, "years": 30, "delay": 5, "currency": "EUR"}'
+test_status "Cost of Waiting (delay past horizon)" "/calculate/cost-of-waiting" \
+    '{"monthly_contribution": 500, "annual_return": 7, "years": 10, "delay": 15, "currency": "EUR"}' 400
+
+# 101. Error responses carry the same CORS, Content-Type and Content-Length headers as successes
+check_error_headers() {
+    local name=$1
+    shift
+    echo "Testing $name..."
+    headers=$(curl -s -D - -o /dev/null -H "Origin: https://example.com" "$@" | tr -d '\r')
+    if echo "$headers" | grep -qi '^access-control-allow-origin: ' \
+        && echo "$headers" | grep -qi '^content-type: application/json' \
+        && echo "$headers" | grep -qi '^content-length: [1-9]'; then
+        echo "✅ $name: $(echo "$headers" | head -1)"
+    else
+        echo "❌ $name: Missing CORS or content headers"
+        echo "Headers: $headers"
+    fi
+    echo "-----------------------------------"
+}
+check_error_headers "Headers (400)" -X POST "$BASE_URL/calculate/tax" -H "Content-Type: application/json" \
+    -d '{"income": -1, "tax_rate": 18, "currency": "UAH"}'
+check_error_headers "Headers (404)" "$BASE_URL/nowhere"
+check_error_headers "Headers (405)" -X GET "$BASE_URL/calculate/batch"
+
+# 102. Longer terms: lower monthly payments, more interest over the loan
+echo "Testing Loan Terms (payment vs interest trade-off)..."
+response=$(curl -s -X POST "$BASE_URL/calculate/loan-terms" -H "Content-Type: application/json" \
+    -d '{"amount": 200000, "rate": 6, "terms": [15, 20, 30], "currency": "USD"}')
+payments=$(echo "$response" | grep -o '"monthly_payment":[0-9.]*' | cut -d: -f2 | tr '\n' ' ')
+interest=$(echo "$response" | grep -o '"total_interest":[0-9.]*' | cut -d: -f2 | tr '\n' ' ')
+falling=$(echo "$payments" | awk '{ ok = NF == 3; for (i = 2; i <= NF; i++) if ($i >= $(i - 1)) ok = 0; print ok }')
+rising=$(echo "$interest" | awk '{ ok = NF == 3; for (i = 2; i <= NF; i++) if ($i <= $(i - 1)) ok = 0; print ok }')
+if [ "$falling" = "1" ] && [ "$rising" = "1" ]; then
+    echo "✅ Loan Terms: payments $payments, interest $interest"
+else
+    echo "❌ Loan Terms: payments $payments, interest $interest"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+test_status "Loan Terms (no terms)" "/calculate/loan-terms" \
+    '{"amount": 200000, "rate": 6, "terms": [], "currency": "USD"}' 400
+
+# 103. At exactly the monthly interest the debt is serviced but never shrinks; a cent less would grow it and is refused
+test_contains "Debt Payoff (interest only)" "/calculate/debt-payoff" \
+    '{"balance": 10000, "interest_rate": 12, "monthly_payment": 100, "extra_payment": 0, "currency": "USD"}' \
+    '"payable":false'
+test_status "Debt Payoff (below interest)" "/calculate/debt-payoff" \
+    '{"balance": 10000, "interest_rate": 12, "monthly_payment": 99.99, "extra_payment": 0, "currency": "USD"}' 400
+test_contains "Debt Payoff (below interest field)" "/calculate/debt-payoff" \
+    '{"balance": 10000, "interest_rate": 12, "monthly_payment": 99.99, "extra_payment": 0, "currency": "USD"}' \
+    '"field":"monthly_payment"'
+
+# 104. Refinance terms are capped like credit's, both loans get a row per month
+test_status "Refinance (huge term)" "/calculate/refinance" \
+    '{"current_balance": 200000, "current_rate": 5, "remaining_term": 1e9, "new_rate": 3.5, "new_term": 20, "closing_costs": 4000, "currency": "EUR"}' 400
+
+# 105. Compound interest is capped at 100 years
+test_status "Compound (huge horizon)" "/calculate/compound" \
+    '{"principal": 1000, "annual_rate": 10, "years": 4e9, "currency": "EUR"}' 400
+
+# 106. Investment periods are capped at 100 years
+test_status "Investment (huge period)" "/calculate/investment" \
+    '{"initial_amount": 1000, "monthly_contribution": 100, "annual_return": 7, "period": 1e8, "include_growth_chart": true, "currency": "EUR"}' 400
+
+# 107. Monte Carlo ages are capped, every trial walks each year in between
+test_status "Retirement Monte Carlo (huge span)" "/calculate/retirement-monte-carlo" \
+    '{"current_age": 30, "retirement_age": 1e9, "desired_income": 2000, "current_savings": 10000, "monthly_savings": 500, "expected_return": 7, "volatility": 15, "trials": 1000, "currency": "EUR"}' 400
+
+# 108. Buy vs rent terms and the opportunity cost horizon are capped at 100 years
+test_status "Buy vs Rent (huge horizon)" "/calculate/buy-rent" \
+    '{"property_price": 250000, "down_payment": 50000, "mortgage_rate": 4, "mortgage_term": 25, "monthly_rent": 1000, "rent_growth": 3, "property_growth": 2, "horizon": 1e9, "currency": "EUR"}' 400
+test_status "Opportunity cost (huge years)" "/calculate/opportunity-cost" \
+    '{"amount": 1000, "annual_return": 7, "years": 1e9, "currency": "EUR"}' 400
+
+# 109. Savings compare takes at most 10 accounts, so the grouped chart stays a sane size
+many_accounts=$(for i in $(seq 1 40); do printf '{"name": "Bank %d", "apy": 3},' "$i"; done)
+test_status "Savings Compare (too many accounts)" "/calculate/savings-compare" \
+    "{\"principal\": 10000, \"monthly_deposit\": 100, \"years\": 5, \"accounts\": [${many_accounts%,}], \"currency\": \"EUR\"}" 400
+test_contains "Savings Compare (too many accounts, message)" "/calculate/savings-compare" \
+    "{\"principal\": 10000, \"monthly_deposit\": 100, \"years\": 5, \"accounts\": [${many_accounts%,}], \"currency\": \"EUR\"}" \
+    'accounts must have at most 10 entries'
+
+# 110. Loan terms compares at most 10 terms, each builds a full schedule
+test_status "Loan Terms (too many terms)" "/calculate/loan-terms" \
+    '{"amount": 200000, "rate": 5, "terms": [5, 10, 15, 20, 25, 30, 35, 40, 45, 50, 55], "currency": "EUR"}' 400
+
+# 111. Whole numbers in the query string fill integer options like precision and chart size
+echo "Testing Tax (GET integer options)..."
+response=$(curl -s "$BASE_URL/calculate/tax?income=50000&tax_rate=18&currency=UAH&precision=8&chart_width=800")
+if echo "$response" | grep -q '"net_income":41000'; then
+    echo "✅ Tax (GET integer options): Success"
+else
+    echo "❌ Tax (GET integer options): Failed"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 112. Debt payoff runs month by month in cents and refuses payoffs longer than 1200 months
+test_contains "Debt Payoff (cent-exact mortgage)" "/calculate/debt-payoff" \
+    '{"balance": 200000, "interest_rate": 6, "monthly_payment": 1199.10, "extra_payment": 0, "currency": "USD"}' \
+    '"total_interest":231677.05,"total_paid":431677.05'
+test_status "Debt Payoff (over 1200 months)" "/calculate/debt-payoff" \
+    '{"balance": 10000, "interest_rate": 1.2, "monthly_payment": 10.01, "extra_payment": 0, "currency": "USD"}' 400
+
+# 113. Tax brackets must rise strictly, and only the last one may be open-ended
+test_status "Tax (brackets out of order)" "/calculate/tax" \
+    '{"income": 60000, "tax_rate": 0, "brackets": [{"up_to": 50000, "rate": 20}, {"up_to": 20000, "rate": 10}, {"up_to": null, "rate": 30}], "currency": "UAH"}' 400
+test_status "Tax (open bracket before the last)" "/calculate/tax" \
+    '{"income": 60000, "tax_rate": 0, "brackets": [{"up_to": null, "rate": 10}, {"up_to": 50000, "rate": 20}], "currency": "UAH"}' 400
+test_contains "Tax (open bracket before the last, message)" "/calculate/tax" \
+    '{"income": 60000, "tax_rate": 0, "brackets": [{"up_to": null, "rate": 10}, {"up_to": 50000, "rate": 20}], "currency": "UAH"}' \
+    'brackets\[0\].up_to must not be null except in the last bracket'
+
+# 114. Income past a finite last bracket is taxed at its rate, which is also the marginal rate
+test_contains "Tax (past the last bracket)" "/calculate/tax" \
+    '{"income": 60000, "tax_rate": 0, "brackets": [{"up_to": 20000, "rate": 10}, {"up_to": 50000, "rate": 20}], "currency": "UAH"}' \
+    '"marginal_rate":20.0'
+
+# 115. An unexpected failure is a JSON 500 naming the endpoint. To force one, start the worker with
+#   wrangler dev --var FAIL_CALCULATOR:tax
+# and run this script with FAIL_CALCULATOR=tax
+if [ -n "$FAIL_CALCULATOR" ]; then
+    test_status "Internal error (status)" "/calculate/$FAIL_CALCULATOR" '{}' 500
+    test_contains "Internal error (endpoint)" "/calculate/$FAIL_CALCULATOR" '{}' \
+        "\"endpoint\":\"/calculate/$FAIL_CALCULATOR\""
+fi
+
+# 116. Query values only become numbers or booleans where the field is one, string fields keep them as text
+echo "Testing Tax (GET string fields)..."
+response=$(curl -s "$BASE_URL/calculate/tax?income=50000&tax_rate=18&currency=true&lang=1&chart_type=0")
+if echo "$response" | grep -q '"net_income":41000'; then
+    echo "✅ Tax (GET string fields): Success"
+else
+    echo "❌ Tax (GET string fields): Failed"
+    echo "Response: $response"
+fi
+echo "-----------------------------------"
+
+# 117. A percentage headline is formatted as one, not as money, and isn't converted
+test_contains "Job Compare (formatted percent)" "/calculate/job-compare" \
+    '{"offer_a": {"monthly_income": 4000, "taxes": 20, "work_hours": 160, "commute_time": 60, "work_expenses": 300}, "offer_b": {"monthly_income": 3500, "taxes": 20, "work_hours": 160, "commute_time": 0, "work_expenses": 50}, "currency": "EUR", "display_currency": "USD"}' \
+    '"formatted_real_rate_difference":"30.4%"'
@@ -0,0 +1,48 @@
+#!/bin/bash
+
+# Renders charts with fixed inputs and compares them byte for byte with the
+# SVGs committed in golden/. After an intended change to the charts, rerun
+# with --update and review the diff of golden/ before committing it.
+
+BASE_URL="http://localhost:8787"
+GOLDEN_DIR="$(dirname "$0")/golden"
+UPDATE=false
+[ "$1" = "--update" ] && UPDATE=true
+FAILED=0
+
+# Prints the unescaped string value of `field` from a JSON response.
+json_string() {
+    sed -n "s/.*\"$1\":\"\(\([^\"\\\\]\|\\\\.\)*\)\".*/\1/p" | sed 's/\\"/"/g; s/\\\\/\\/g'
+}
+
+check_golden() {
+    local name=$1
+    local endpoint=$2
+    local data=$3
+    local golden="$GOLDEN_DIR/$name.svg"
+
+    svg=$(curl -s -X POST "$BASE_URL$endpoint" -H "Content-Type: application/json" -d "$data" | json_string chart)
+    if [ "$UPDATE" = true ]; then
+        echo "$svg" > "$golden"
+        echo "📝 $name: Updated"
+    elif [ "$svg" = "$(cat "$golden")" ]; then
+        echo "✅ $name: Matches"
+    else
+        echo "❌ $name: Differs from $golden"
+        diff <(echo "$svg" | sed 's/></>\n</g') <(sed 's/></>\n</g' "$golden") | head -20
+        FAILED=1
+    fi
+}
+
+check_golden "tax-bar" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "currency": "UAH", "lang": "en"}'
+check_golden "tax-pie" "/calculate/tax" \
+    '{"income": 50000, "tax_rate": 18, "chart_type": "pie", "currency": "UAH", "lang": "en"}'
+check_golden "retirement-stacked" "/calculate/retirement" \
+    '{"current_age": 30, "retirement_age": 60, "desired_income": 2000, "current_savings": 10000, "monthly_savings": 500, "expected_return": 7, "currency": "EUR", "lang": "en"}'
+check_golden "compound-line" "/calculate/compound" \
+    '{"principal": 1000, "annual_rate": 10, "years": 10, "compounding": "annual", "currency": "EUR", "lang": "en"}'
+check_golden "goal-progress" "/calculate/goal-progress" \
+    '{"target_amount": 10000, "current_amount": 4000, "monthly_contribution": 500, "currency": "EUR", "lang": "en"}'
+
+exit $FAILED